@@ -0,0 +1,153 @@
+// SPDX-FileCopyrightText: © 2025 - 2026 Elias Steininger <elias.st4600@gmail.com> and Project Contributors (see CONTRIBUTORS.md)
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use crate::command::CommandRegistry;
+use egui::text::{CCursor, CCursorRange};
+use egui::{Color32, RichText};
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+const MAX_MESSAGES: usize = 100; // Older messages are dropped
+const VISIBLE_MESSAGES: usize = 10;
+const MAX_SUGGESTIONS: usize = 8;
+const MESSAGE_DURATION: Duration = Duration::from_secs(10); // How long new messages stay on screen while the chat is closed
+const CHAT_WIDTH: f32 = 500.0;
+
+struct Message {
+    text: String,
+    is_error: bool,
+    time: Instant
+}
+
+/// Lines the player sent, applied by `GameState` after the UI pass
+pub enum ChatAction {
+    Send(String),
+    Close
+}
+
+/// Input box for chat messages and commands, with the messages above it
+pub struct Chat {
+    input: String,
+    messages: VecDeque<Message>,
+    focus_requested: bool
+}
+
+impl Chat {
+    pub fn new() -> Self {
+        Chat {
+            input: String::new(),
+            messages: VecDeque::new(),
+            focus_requested: false
+        }
+    }
+
+    /// Opens the input box with `text` already typed
+    pub fn open(&mut self, text: &str) {
+        self.input = text.to_string();
+        self.focus_requested = true;
+    }
+
+    pub fn add_message(&mut self, text: String, is_error: bool) {
+        if self.messages.len() == MAX_MESSAGES {
+            self.messages.pop_front();
+        }
+
+        self.messages.push_back(Message {text, is_error, time: Instant::now()});
+    }
+
+    pub fn clear(&mut self) {
+        self.messages.clear();
+    }
+
+    /// The input box, suggestions for the command being typed and the latest messages
+    pub fn show(&mut self, ctx: &egui::Context, commands: &CommandRegistry) -> Option<ChatAction> {
+        let mut action = None;
+
+        egui::Area::new(egui::Id::new("Chat")).anchor(egui::Align2::LEFT_BOTTOM, egui::vec2(5.0, -5.0)).show(ctx, |ui| {
+            ui.set_width(CHAT_WIDTH);
+            self.show_messages(ui, None);
+
+            let suggestions = commands.complete(&self.input);
+
+            if !suggestions.is_empty() {
+                egui::Frame::new().fill(Color32::from_black_alpha(150)).inner_margin(3.0).show(ui, |ui| {
+                    for suggestion in suggestions.iter().take(MAX_SUGGESTIONS) {
+                        ui.label(RichText::new(suggestion).monospace().color(Color32::YELLOW));
+                    }
+                });
+            }
+
+            let id = egui::Id::new("Chat input");
+
+            if ui.input_mut(|input| input.consume_key(egui::Modifiers::NONE, egui::Key::Tab)) {
+                self.complete(&suggestions);
+
+                if let Some(mut state) = egui::TextEdit::load_state(ui.ctx(), id) {
+                    state.cursor.set_char_range(Some(CCursorRange::one(CCursor::new(self.input.chars().count()))));
+                    state.store(ui.ctx(), id);
+                }
+            }
+
+            // Tab is caught above, locking the focus only keeps it from moving to another widget
+            let response = ui.add(egui::TextEdit::singleline(&mut self.input).id(id).desired_width(CHAT_WIDTH).lock_focus(true).font(egui::TextStyle::Monospace));
+
+            if std::mem::take(&mut self.focus_requested) {
+                response.request_focus();
+            }
+
+            if response.lost_focus() && ui.input(|input| input.key_pressed(egui::Key::Enter)) {
+                let text = std::mem::take(&mut self.input);
+                action = Some(if text.trim().is_empty() {ChatAction::Close} else {ChatAction::Send(text)});
+            }
+        });
+
+        action
+    }
+
+    /// Messages newer than a few seconds, shown above the hotbar while the chat is closed
+    pub fn show_recent(&self, ctx: &egui::Context) {
+        if self.messages.back().is_none_or(|message| message.time.elapsed() >= MESSAGE_DURATION) {
+            return;
+        }
+
+        egui::Area::new(egui::Id::new("Chat")).anchor(egui::Align2::LEFT_BOTTOM, egui::vec2(5.0, -5.0)).interactable(false).show(ctx, |ui| {
+            ui.set_width(CHAT_WIDTH);
+            self.show_messages(ui, Some(MESSAGE_DURATION));
+        });
+    }
+
+    fn show_messages(&self, ui: &mut egui::Ui, max_age: Option<Duration>) {
+        let messages: Vec<&Message> = self.messages.iter().rev()
+            .take_while(|message| max_age.is_none_or(|max_age| message.time.elapsed() < max_age))
+            .take(VISIBLE_MESSAGES)
+            .collect();
+
+        if messages.is_empty() {
+            return;
+        }
+
+        egui::Frame::new().fill(Color32::from_black_alpha(100)).inner_margin(3.0).show(ui, |ui| {
+            for message in messages.iter().rev() {
+                let color = if message.is_error {Color32::from_rgb(255, 90, 90)} else {Color32::WHITE};
+                ui.label(RichText::new(&message.text).monospace().color(color));
+            }
+        });
+    }
+
+    /// Takes the only suggestion, or as much as all suggestions have in common
+    fn complete(&mut self, suggestions: &[String]) {
+        match suggestions {
+            [] => {}
+            [suggestion] => self.input = format!("{suggestion} "),
+            [first, rest @ ..] => {
+                let common = rest.iter().fold(first.len(), |common, suggestion| {
+                    first.char_indices().zip(suggestion.chars()).take_while(|((_, a), b)| a == b).last().map_or(0, |((i, a), _)| i + a.len_utf8()).min(common)
+                });
+
+                if common > self.input.len() {
+                    self.input = first[..common].to_string();
+                }
+            }
+        }
+    }
+}
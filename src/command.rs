@@ -0,0 +1,456 @@
+// SPDX-FileCopyrightText: © 2025 - 2026 Elias Steininger <elias.st4600@gmail.com> and Project Contributors (see CONTRIBUTORS.md)
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use crate::world::block;
+use crate::world::chunk::{CHUNK_DIMENSION, CHUNK_HEIGHT};
+use crate::world::fluid;
+use crate::world::item::{Item, Tool, ToolKind, ToolTier};
+use crate::world::player::{GameMode, Player};
+use crate::world::world::{World, WORLD_BORDER};
+use glam::{IVec2, IVec3, Vec3};
+
+const MAX_FILL_VOLUME: i64 = 32768; // Blocks `/fill` changes at most, larger areas would stall the game while remeshing
+const MAX_GIVE_COUNT: i32 = 576; // A full hotbar of block stacks
+const HELP_NAME: &str = "help"; // Answered by the registry itself, since it lists the registered commands
+const HELP_DESCRIPTION: &str = "Lists every command";
+
+/// How an argument is parsed and completed
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ArgType {
+    Coordinate, // A number, or relative to the player's position with `~`
+    Integer,
+    Block,
+    Item, // A block or a tool like `iron_pickaxe`
+    GameMode
+}
+
+/// A parsed argument
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Value {
+    Coordinate {value: f32, relative: bool},
+    Integer(i32),
+    Block(u8),
+    Item(Item),
+    GameMode(GameMode)
+}
+
+pub struct Arg {
+    pub name: &'static str,
+    pub arg_type: ArgType,
+    pub optional: bool // Only trailing arguments can be optional
+}
+
+impl Arg {
+    pub const fn new(name: &'static str, arg_type: ArgType) -> Self {
+        Arg {name, arg_type, optional: false}
+    }
+
+    pub const fn optional(name: &'static str, arg_type: ArgType) -> Self {
+        Arg {name, arg_type, optional: true}
+    }
+}
+
+/// What commands can change
+pub struct CommandContext<'a> {
+    pub world: &'a mut World,
+    pub player: &'a mut Player,
+    pub seed: Option<u64> // Seed of the world being played
+}
+
+/// Runs the command with its parsed arguments and returns the message shown in the chat
+type CommandFn = fn(&[Value], &mut CommandContext) -> Result<String, String>;
+
+pub struct Command {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub args: &'static [Arg],
+    pub run: CommandFn
+}
+
+impl Command {
+    /// Like `/tp <x> <y> <z>`, optional arguments in square brackets
+    pub fn get_usage(&self) -> String {
+        get_usage(self.name, self.args)
+    }
+
+    fn parse_args(&self, words: &[&str]) -> Result<Vec<Value>, String> {
+        let required = self.args.iter().filter(|arg| !arg.optional).count();
+
+        if words.len() < required || words.len() > self.args.len() {
+            return Err(format!("Usage: {}", self.get_usage()));
+        }
+
+        self.args.iter().zip(words).map(|(arg, word)| parse_value(arg.arg_type, word).ok_or(format!("Invalid {} '{word}', usage: {}", arg.name, self.get_usage()))).collect()
+    }
+}
+
+/// Every command that can be typed into the chat
+pub struct CommandRegistry {
+    commands: Vec<Command>
+}
+
+impl Default for CommandRegistry {
+    fn default() -> Self {
+        let mut registry = CommandRegistry {commands: Vec::new()};
+
+        registry.register(Command {name: "tp", description: "Teleports the player", args: &POSITION_ARGS, run: teleport});
+        registry.register(Command {name: "give", description: "Adds items to the hotbar", args: &GIVE_ARGS, run: give});
+        registry.register(Command {name: "gamemode", description: "Changes the game mode", args: &GAME_MODE_ARGS, run: set_game_mode});
+        registry.register(Command {name: "seed", description: "Shows the seed of the world", args: &[], run: show_seed});
+        registry.register(Command {name: "setblock", description: "Places a block", args: &SETBLOCK_ARGS, run: set_block});
+        registry.register(Command {name: "fill", description: "Fills a box with a block", args: &FILL_ARGS, run: fill});
+        registry.register(Command {name: "spawnpoint", description: "Sets where the player respawns, the current position by default", args: &SPAWN_ARGS, run: set_spawn_point});
+        registry.register(Command {name: "setworldspawn", description: "Sets the world spawn, the current position by default", args: &SPAWN_ARGS, run: set_world_spawn});
+
+        registry
+    }
+}
+
+impl CommandRegistry {
+    /// Adds a command, replacing one with the same name
+    pub fn register(&mut self, command: Command) {
+        self.commands.retain(|registered| registered.name != command.name);
+        self.commands.push(command);
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Command> {
+        self.commands.iter().find(|command| command.name == name)
+    }
+
+    /// Parses and runs a line like `/tp ~ 100 ~`, the leading slash is optional
+    pub fn execute(&self, input: &str, context: &mut CommandContext) -> Result<String, String> {
+        let words: Vec<&str> = input.trim().trim_start_matches('/').split_whitespace().collect();
+
+        let Some((name, words)) = words.split_first() else {
+            return Err("Type a command, /help lists them".to_string());
+        };
+
+        if *name == HELP_NAME {
+            return if words.is_empty() {Ok(self.get_help())} else {Err(format!("Usage: /{HELP_NAME}"))};
+        }
+
+        let command = self.get(name).ok_or(format!("Unknown command '/{name}', /help lists every command"))?;
+        let args = command.parse_args(words)?;
+
+        (command.run)(&args, context)
+    }
+
+    /// The usage and description of `/help` and every registered command, one per line
+    fn get_help(&self) -> String {
+        std::iter::once(format!("/{HELP_NAME} - {HELP_DESCRIPTION}"))
+            .chain(self.commands.iter().map(|command| format!("{} - {}", command.get_usage(), command.description)))
+            .collect::<Vec<_>>().join("\n")
+    }
+
+    /// Every way to complete the last word of `input`, each as the whole completed line
+    pub fn complete(&self, input: &str) -> Vec<String> {
+        let Some(line) = input.strip_prefix('/') else {
+            return Vec::new();
+        };
+
+        let (start, word) = line.rsplit_once(' ').map_or(("", line), |(start, word)| (start, word));
+        let previous: Vec<&str> = start.split_whitespace().collect();
+
+        let candidates: Vec<String> = match previous.split_first() {
+            None => std::iter::once(HELP_NAME).chain(self.commands.iter().map(|command| command.name)).map(str::to_string).collect(),
+            Some((name, args)) => match self.get(name).and_then(|command| command.args.get(args.len())) {
+                Some(arg) => get_suggestions(arg.arg_type),
+                None => Vec::new()
+            }
+        };
+
+        let prefix = if previous.is_empty() {"/".to_string()} else {format!("/{start} ")};
+
+        candidates.into_iter().filter(|candidate| candidate.starts_with(word)).map(|candidate| format!("{prefix}{candidate}")).collect()
+    }
+}
+
+const POSITION_ARGS: [Arg; 3] = [Arg::new("x", ArgType::Coordinate), Arg::new("y", ArgType::Coordinate), Arg::new("z", ArgType::Coordinate)];
+const SPAWN_ARGS: [Arg; 3] = [Arg::optional("x", ArgType::Coordinate), Arg::optional("y", ArgType::Coordinate), Arg::optional("z", ArgType::Coordinate)];
+const GIVE_ARGS: [Arg; 2] = [Arg::new("item", ArgType::Item), Arg::optional("count", ArgType::Integer)];
+const GAME_MODE_ARGS: [Arg; 1] = [Arg::new("mode", ArgType::GameMode)];
+const SETBLOCK_ARGS: [Arg; 4] = [Arg::new("x", ArgType::Coordinate), Arg::new("y", ArgType::Coordinate), Arg::new("z", ArgType::Coordinate), Arg::new("block", ArgType::Block)];
+const FILL_ARGS: [Arg; 7] = [
+    Arg::new("x1", ArgType::Coordinate), Arg::new("y1", ArgType::Coordinate), Arg::new("z1", ArgType::Coordinate),
+    Arg::new("x2", ArgType::Coordinate), Arg::new("y2", ArgType::Coordinate), Arg::new("z2", ArgType::Coordinate),
+    Arg::new("block", ArgType::Block)
+];
+
+fn parse_value(arg_type: ArgType, word: &str) -> Option<Value> {
+    match arg_type {
+        ArgType::Coordinate => match word.strip_prefix('~') {
+            Some("") => Some(Value::Coordinate {value: 0.0, relative: true}),
+            Some(offset) => Some(Value::Coordinate {value: parse_finite(offset)?, relative: true}),
+            None => Some(Value::Coordinate {value: parse_finite(word)?, relative: false})
+        }
+        ArgType::Integer => word.parse().ok().map(Value::Integer),
+        ArgType::Block => block::get_id(word).map(Value::Block),
+        ArgType::Item => parse_item(word).map(Value::Item),
+        ArgType::GameMode => GameMode::from_name(word).map(Value::GameMode)
+    }
+}
+
+/// A number that is neither infinite nor NaN
+fn parse_finite(word: &str) -> Option<f32> {
+    word.parse().ok().filter(|value: &f32| value.is_finite())
+}
+
+/// A block name or `<tier>_<kind>` for tools
+fn parse_item(word: &str) -> Option<Item> {
+    if let Some(id) = block::get_id(word) && id != block::AIR {
+        return Some(Item::Block(id));
+    }
+
+    let (tier, kind) = word.split_once('_')?;
+    let tier = *ToolTier::ALL.iter().find(|tool_tier| tool_tier.get_name() == tier)?;
+    let kind = *ToolKind::ALL.iter().find(|tool_kind| tool_kind.get_name() == kind)?;
+
+    Some(Item::Tool(Tool::new(kind, tier)))
+}
+
+fn get_suggestions(arg_type: ArgType) -> Vec<String> {
+    let block_names = (0..block::BLOCK_COUNT as u8).map(|id| block::get_properties(id).name.to_string());
+
+    match arg_type {
+        ArgType::Coordinate => vec!["~".to_string()],
+        ArgType::Integer => Vec::new(),
+        ArgType::Block => block_names.collect(),
+        ArgType::Item => block_names.skip(1)
+            .chain(ToolTier::ALL.iter().flat_map(|tier| ToolKind::ALL.iter().map(move |kind| format!("{}_{}", tier.get_name(), kind.get_name()))))
+            .collect(),
+        ArgType::GameMode => GameMode::ALL.iter().map(|mode| mode.get_name().to_string()).collect()
+    }
+}
+
+fn get_usage(name: &str, args: &[Arg]) -> String {
+    args.iter().fold(format!("/{name}"), |usage, arg| if arg.optional {format!("{usage} [{}]", arg.name)} else {format!("{usage} <{}>", arg.name)})
+}
+
+/// The error for arguments that don't match what the command expects
+fn usage_error(name: &str, args: &[Arg]) -> String {
+    format!("Usage: {}", get_usage(name, args))
+}
+
+/// Turns up to three coordinate arguments into a position, relative and missing ones are taken from `origin`
+fn get_position(values: &[Value], origin: Vec3) -> Vec3 {
+    let mut pos = origin;
+
+    for (axis, value) in values.iter().take(3).enumerate() {
+        if let Value::Coordinate {value, relative} = *value {
+            pos[axis] = if relative {origin[axis] + value} else {value};
+        }
+    }
+
+    pos
+}
+
+fn get_block_pos(values: &[Value], player: &Player) -> Result<IVec3, String> {
+    let pos = get_position(values, player.get_pos()).floor().as_ivec3();
+
+    if pos.y < 0 || pos.y >= CHUNK_HEIGHT as i32 {
+        return Err(format!("Height {} is outside of the world", pos.y));
+    }
+
+    Ok(pos)
+}
+
+fn is_loaded(world: &World, pos: IVec3) -> bool {
+    world.has_chunk(IVec2::new(pos.x, pos.z).div_euclid(IVec2::splat(CHUNK_DIMENSION as i32)))
+}
+
+fn teleport(args: &[Value], context: &mut CommandContext) -> Result<String, String> {
+    let mut pos = get_position(args, context.player.get_pos());
+    pos.x = pos.x.clamp(-WORLD_BORDER as f32, WORLD_BORDER as f32);
+    pos.z = pos.z.clamp(-WORLD_BORDER as f32, WORLD_BORDER as f32);
+
+    context.player.set_pos(pos);
+
+    Ok(format!("Teleported to {:.1} {:.1} {:.1}", pos.x, pos.y, pos.z))
+}
+
+fn give(args: &[Value], context: &mut CommandContext) -> Result<String, String> {
+    let &[Value::Item(item), ref count @ ..] = args else {
+        return Err(usage_error("give", &GIVE_ARGS));
+    };
+
+    let count = match count.first() {
+        Some(Value::Integer(count)) if (1..=MAX_GIVE_COUNT).contains(count) => *count,
+        Some(_) => return Err(format!("The count has to be between 1 and {MAX_GIVE_COUNT}")),
+        None => 1
+    };
+
+    let given = (0..count).take_while(|_| context.player.get_inventory_mut().add_item(item)).count();
+
+    if given == 0 {
+        return Err("The hotbar is full".to_string());
+    }
+
+    Ok(format!("Gave {given} {}", item.get_name()))
+}
+
+fn set_game_mode(args: &[Value], context: &mut CommandContext) -> Result<String, String> {
+    let &[Value::GameMode(game_mode)] = args else {
+        return Err(usage_error("gamemode", &GAME_MODE_ARGS));
+    };
+
+    context.player.set_game_mode(game_mode);
+
+    Ok(format!("Set the game mode to {}", game_mode.get_name()))
+}
+
+fn show_seed(_args: &[Value], context: &mut CommandContext) -> Result<String, String> {
+    context.seed.map(|seed| format!("Seed: {seed}")).ok_or("This world has no seed".to_string())
+}
+
+fn set_block(args: &[Value], context: &mut CommandContext) -> Result<String, String> {
+    let &[_, _, _, Value::Block(id)] = args else {
+        return Err(usage_error("setblock", &SETBLOCK_ARGS));
+    };
+
+    let pos = get_block_pos(args, context.player)?;
+
+    if !is_loaded(context.world, pos) {
+        return Err(format!("{} {} {} is not loaded", pos.x, pos.y, pos.z));
+    }
+
+    context.world.update_block(pos, id, fluid::SOURCE_LEVEL);
+
+    Ok(format!("Placed {} at {} {} {}", block::get_properties(id).name, pos.x, pos.y, pos.z))
+}
+
+fn set_spawn_point(args: &[Value], context: &mut CommandContext) -> Result<String, String> {
+    let pos = get_block_pos(args, context.player)?;
+
+    context.player.set_spawn_point(Some(pos));
+
+    Ok(format!("Set the spawn point to {} {} {}", pos.x, pos.y, pos.z))
+}
+
+fn set_world_spawn(args: &[Value], context: &mut CommandContext) -> Result<String, String> {
+    let pos = get_block_pos(args, context.player)?;
+
+    context.world.set_spawn(pos);
+
+    Ok(format!("Set the world spawn to {} {} {}", pos.x, pos.y, pos.z))
+}
+
+fn fill(args: &[Value], context: &mut CommandContext) -> Result<String, String> {
+    let &[_, _, _, _, _, _, Value::Block(id)] = args else {
+        return Err(usage_error("fill", &FILL_ARGS));
+    };
+
+    let first = get_block_pos(&args[0..3], context.player)?;
+    let second = get_block_pos(&args[3..6], context.player)?;
+    let (min, max) = (first.min(second), first.max(second));
+    let volume = (max - min + IVec3::ONE).as_i64vec3().element_product();
+
+    if volume > MAX_FILL_VOLUME {
+        return Err(format!("Can't fill {volume} blocks, the limit is {MAX_FILL_VOLUME}"));
+    }
+
+    let mut filled = 0;
+
+    for x in min.x..=max.x {
+        for y in min.y..=max.y {
+            for z in min.z..=max.z {
+                let pos = IVec3::new(x, y, z);
+
+                if is_loaded(context.world, pos) {
+                    context.world.update_block(pos, id, fluid::SOURCE_LEVEL);
+                    filled += 1;
+                }
+            }
+        }
+    }
+
+    Ok(format!("Filled {filled} blocks with {}", block::get_properties(id).name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::item::{ItemStack, HOTBAR_SIZE};
+    use crate::world::test_util::world_with_floor;
+
+    fn run(input: &str, world: &mut World, player: &mut Player) -> Result<String, String> {
+        CommandRegistry::default().execute(input, &mut CommandContext {world, player, seed: Some(42)})
+    }
+
+    #[test]
+    fn commands_change_the_world_and_player() {
+        let mut world = world_with_floor(10, block::STONE);
+        let mut player = Player::new();
+        player.set_pos(Vec3::new(0.5, 11.0, 0.5));
+
+        let help = run("/help", &mut world, &mut player).unwrap();
+        assert!(help.starts_with("/help - Lists every command\n"));
+        assert!(help.contains("/tp <x> <y> <z> - Teleports the player"));
+
+        run("/setblock 3 11 ~2 bricks", &mut world, &mut player).unwrap();
+        assert_eq!(world.get_global_block(IVec3::new(3, 11, 2)), block::BRICKS);
+
+        assert_eq!(run("/fill -2 10 -2 2 11 ~1 sand", &mut world, &mut player).unwrap(), "Filled 40 blocks with sand");
+        assert_eq!(world.get_global_block(IVec3::new(-2, 11, -1)), block::SAND);
+        assert_eq!(world.get_global_block(IVec3::new(3, 11, 2)), block::BRICKS);
+
+        run("/tp ~1 ~5 -3", &mut world, &mut player).unwrap();
+        assert_eq!(player.get_pos(), Vec3::new(1.5, 16.0, -3.0));
+
+        run("/gamemode creative", &mut world, &mut player).unwrap();
+        assert_eq!(player.get_game_mode(), GameMode::Creative);
+
+        let free_slot = (0..HOTBAR_SIZE).find(|&i| player.get_inventory().get_slot(i).is_none()).unwrap();
+        run("give diamond_pickaxe", &mut world, &mut player).unwrap();
+        assert_eq!(player.get_inventory().get_slot(free_slot), Some(&ItemStack::new(Item::Tool(Tool::new(ToolKind::Pickaxe, ToolTier::Diamond)), 1)));
+
+        assert_eq!(run("/seed", &mut world, &mut player).unwrap(), "Seed: 42");
+
+        run("/spawnpoint", &mut world, &mut player).unwrap();
+        assert_eq!(player.get_spawn_point(), Some(IVec3::new(1, 16, -3)));
+
+        run("/setworldspawn 5 ~ 7", &mut world, &mut player).unwrap();
+        assert_eq!(world.get_spawn(), IVec3::new(5, 16, 7));
+    }
+
+    #[test]
+    fn invalid_commands_are_rejected() {
+        let mut world = world_with_floor(10, block::STONE);
+        let mut player = Player::new();
+
+        assert!(run("/fly", &mut world, &mut player).unwrap_err().starts_with("Unknown command"));
+        assert_eq!(run("/tp 1 2", &mut world, &mut player).unwrap_err(), "Usage: /tp <x> <y> <z>");
+        assert_eq!(run("/setblock 0 1 0 cheese", &mut world, &mut player).unwrap_err(), "Invalid block 'cheese', usage: /setblock <x> <y> <z> <block>");
+        assert!(run("/setblock 0 500 0 stone", &mut world, &mut player).is_err());
+        assert!(run("/setblock 100 10 0 stone", &mut world, &mut player).is_err());
+        assert!(run("/fill 0 0 0 100 100 100 air", &mut world, &mut player).is_err());
+        assert!(run("/give air", &mut world, &mut player).is_err());
+        assert_eq!(run("/gamemode hardcore", &mut world, &mut player).unwrap_err(), "Invalid mode 'hardcore', usage: /gamemode <mode>");
+        assert_eq!(run("/help tp", &mut world, &mut player).unwrap_err(), "Usage: /help");
+        assert_eq!(run("/tp nan 11 0", &mut world, &mut player).unwrap_err(), "Invalid x 'nan', usage: /tp <x> <y> <z>");
+        assert_eq!(run("/tp 0 ~inf 0", &mut world, &mut player).unwrap_err(), "Invalid y '~inf', usage: /tp <x> <y> <z>");
+        assert_eq!(run("/setblock 0 10 1e40 stone", &mut world, &mut player).unwrap_err(), "Invalid z '1e40', usage: /setblock <x> <y> <z> <block>");
+
+        run("/tp 1e10 11 -1e10", &mut world, &mut player).unwrap();
+        assert_eq!(player.get_pos(), Vec3::new(WORLD_BORDER as f32, 11.0, -WORLD_BORDER as f32));
+
+        // Arguments that don't match the command's spec are an error instead of a panic
+        let mut context = CommandContext {world: &mut world, player: &mut player, seed: None};
+        assert_eq!(set_game_mode(&[Value::Integer(5)], &mut context).unwrap_err(), "Usage: /gamemode <mode>");
+        assert_eq!(fill(&[Value::Block(block::AIR)], &mut context).unwrap_err(), "Usage: /fill <x1> <y1> <z1> <x2> <y2> <z2> <block>");
+        assert_eq!(world.get_global_block(IVec3::new(0, 10, 0)), block::STONE);
+    }
+
+    #[test]
+    fn last_word_is_completed() {
+        let registry = CommandRegistry::default();
+
+        assert_eq!(registry.complete("/ga"), vec!["/gamemode"]);
+        assert_eq!(registry.complete("/he"), vec!["/help"]);
+        assert_eq!(registry.complete("/gamemode s"), vec!["/gamemode survival", "/gamemode spectator"]);
+        assert_eq!(registry.complete("/give iron_p"), vec!["/give iron_pickaxe"]);
+        assert_eq!(registry.complete("/tp 1 "), vec!["/tp 1 ~"]);
+        assert!(registry.complete("/seed ").is_empty());
+        assert!(registry.complete("hello").is_empty());
+        assert_eq!(Command {name: "test", description: "", args: &GIVE_ARGS, run: give}.get_usage(), "/test <item> [count]");
+    }
+}
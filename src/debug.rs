@@ -0,0 +1,81 @@
+// SPDX-FileCopyrightText: © 2025 - 2026 Elias Steininger <elias.st4600@gmail.com> and Project Contributors (see CONTRIBUTORS.md)
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::collections::VecDeque;
+
+pub const FRAME_HISTORY: usize = 240; // Frames shown in the frame-time graph
+
+/// Durations of the most recent frames, the oldest is dropped when a new one is recorded
+pub struct FrameTimes {
+    times: VecDeque<f32> // In seconds
+}
+
+impl FrameTimes {
+    pub fn new() -> Self {
+        FrameTimes {
+            times: VecDeque::with_capacity(FRAME_HISTORY)
+        }
+    }
+
+    pub fn record(&mut self, delta_time: f32) {
+        if self.times.len() == FRAME_HISTORY {
+            self.times.pop_front();
+        }
+
+        self.times.push_back(delta_time);
+    }
+
+    pub fn get_times(&self) -> &VecDeque<f32> {&self.times}
+
+    /// Frames per second averaged over the recorded frames
+    pub fn get_fps(&self) -> f32 {
+        let total: f32 = self.times.iter().sum();
+
+        if total <= 0.0 {
+            return 0.0;
+        }
+
+        self.times.len() as f32 / total
+    }
+
+    pub fn get_max(&self) -> f32 {
+        self.times.iter().copied().fold(0.0, f32::max)
+    }
+}
+
+/// Compass direction of a yaw in degrees, north is -Z and east is +X
+pub fn get_facing(yaw: f32) -> &'static str {
+    const DIRECTIONS: [&str; 4] = ["east (+X)", "south (+Z)", "west (-X)", "north (-Z)"];
+
+    DIRECTIONS[((yaw / 90.0).round() as i32).rem_euclid(4) as usize]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frame_times_keep_only_the_latest_frames() {
+        let mut frame_times = FrameTimes::new();
+
+        for _ in 0..FRAME_HISTORY {
+            frame_times.record(0.1);
+        }
+        frame_times.record(0.02);
+
+        assert_eq!(frame_times.get_times().len(), FRAME_HISTORY);
+        assert_eq!(frame_times.get_times().back(), Some(&0.02));
+        assert!((frame_times.get_max() - 0.1).abs() < 1e-6);
+        assert!(frame_times.get_fps() > 10.0 && frame_times.get_fps() < 10.1);
+        assert_eq!(FrameTimes::new().get_fps(), 0.0);
+    }
+
+    #[test]
+    fn yaw_maps_to_the_nearest_direction() {
+        assert_eq!(get_facing(-90.0), "north (-Z)");
+        assert_eq!(get_facing(10.0), "east (+X)");
+        assert_eq!(get_facing(100.0), "south (+Z)");
+        assert_eq!(get_facing(-170.0), "west (-X)");
+        assert_eq!(get_facing(630.0), "north (-Z)");
+    }
+}
@@ -0,0 +1,121 @@
+// SPDX-FileCopyrightText: © 2025 - 2026 Elias Steininger <elias.st4600@gmail.com> and Project Contributors (see CONTRIBUTORS.md)
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use crate::input::Action;
+use gilrs::{Axis, Button, EventType, Gilrs};
+use glam::Vec2;
+use std::collections::HashSet;
+
+pub const LOOK_SPEED: f32 = 4000.0; // The right stick at full tilt turns like moving the mouse this many pixels per second
+
+/// Buttons that keep their action active while held
+const HELD_BUTTONS: [(Button, Action); 4] = [
+    (Button::South, Action::Jump),
+    (Button::LeftThumb, Action::Sprint),
+    (Button::East, Action::Sneak),
+    (Button::RightTrigger2, Action::Attack)
+];
+
+/// Something a gamepad button does once per press
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum GamepadEvent {
+    Action(Action), // Triggered like pressing the action's binding
+    Pause
+}
+
+/// Reads the first connected gamepad and turns it into the same actions as the keyboard
+pub struct Gamepad {
+    gilrs: Option<Gilrs>, // None if gamepads aren't supported on this system
+    actions: HashSet<Action>,
+    movement: Vec2, // Left stick after the dead zone, forward is positive
+    look: Vec2, // Right stick after the dead zone, up is positive
+    error: Option<String> // Why gamepads aren't supported, until it's shown
+}
+
+impl Gamepad {
+    pub fn new() -> Self {
+        let (gilrs, error) = match Gilrs::new() {
+            Ok(gilrs) => (Some(gilrs), None),
+            Err(error) => (None, Some(format!("Gamepads are not available: {error}")))
+        };
+
+        Gamepad {
+            gilrs,
+            actions: HashSet::new(),
+            movement: Vec2::ZERO,
+            look: Vec2::ZERO,
+            error
+        }
+    }
+
+    /// Reads the gamepad state and returns the buttons pressed since the last update
+    pub fn update(&mut self, dead_zone: f32) -> Vec<GamepadEvent> {
+        self.actions.clear();
+        self.movement = Vec2::ZERO;
+        self.look = Vec2::ZERO;
+
+        let Some(gilrs) = &mut self.gilrs else {
+            return Vec::new();
+        };
+
+        let mut events = Vec::new();
+
+        while let Some(event) = gilrs.next_event() {
+            if let EventType::ButtonPressed(button, _) = event.event && let Some(gamepad_event) = get_button_event(button) {
+                events.push(gamepad_event);
+            }
+        }
+
+        let Some((_, gamepad)) = gilrs.gamepads().next() else {
+            return events;
+        };
+
+        self.movement = apply_dead_zone(Vec2::new(gamepad.value(Axis::LeftStickX), gamepad.value(Axis::LeftStickY)), dead_zone);
+        self.actions.extend(HELD_BUTTONS.iter().filter(|(button, _)| gamepad.is_pressed(*button)).map(|(_, action)| *action));
+        self.look = apply_dead_zone(Vec2::new(gamepad.value(Axis::RightStickX), gamepad.value(Axis::RightStickY)), dead_zone);
+
+        events
+    }
+
+    pub fn get_actions(&self) -> &HashSet<Action> {&self.actions}
+
+    pub fn get_movement(&self) -> Vec2 {self.movement}
+
+    pub fn get_look(&self) -> Vec2 {self.look}
+
+    /// Returns the error from setting up gamepad support, only once
+    pub fn take_error(&mut self) -> Option<String> {self.error.take()}
+}
+
+fn get_button_event(button: Button) -> Option<GamepadEvent> {
+    match button {
+        Button::LeftTrigger2 => Some(GamepadEvent::Action(Action::UseItem)),
+        Button::RightTrigger => Some(GamepadEvent::Action(Action::HotbarNext)),
+        Button::LeftTrigger => Some(GamepadEvent::Action(Action::HotbarPrevious)),
+        Button::Start => Some(GamepadEvent::Pause),
+        _ => None
+    }
+}
+
+/// Ignores the stick inside the dead zone and scales the rest back to 0 to 1, so movement starts smoothly at its edge
+fn apply_dead_zone(stick: Vec2, dead_zone: f32) -> Vec2 {
+    let length = stick.length();
+
+    if length <= dead_zone || dead_zone >= 1.0 {
+        return Vec2::ZERO;
+    }
+
+    stick / length * ((length - dead_zone) / (1.0 - dead_zone)).min(1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dead_zone_is_ignored_and_the_rest_rescaled() {
+        assert_eq!(apply_dead_zone(Vec2::new(0.1, 0.05), 0.15), Vec2::ZERO);
+        assert!((apply_dead_zone(Vec2::new(1.0, 0.0), 0.15) - Vec2::new(1.0, 0.0)).length() < 1e-5);
+        assert!((apply_dead_zone(Vec2::new(0.0, -0.575), 0.15) - Vec2::new(0.0, -0.5)).length() < 1e-5);
+    }
+}
@@ -2,35 +2,74 @@
 // SPDX-License-Identifier: GPL-3.0-or-later
 
 use std::collections::HashSet;
+use std::path::{Path, PathBuf};
 use std::num::NonZeroU32;
-use crate::world::player::Player;
-use crate::world::world::World;
+use crate::chat::{Chat, ChatAction};
+use crate::command::{CommandContext, CommandRegistry};
+use crate::debug;
+use crate::debug::FrameTimes;
+use crate::world::block;
+use crate::world::chunk::CHUNK_DIMENSION;
+use crate::world::item;
+use crate::world::item::{Item, ItemStack, Tool, HOTBAR_SIZE};
+use crate::world::player::{GameMode, Player};
+use crate::world::save;
+use crate::world::save::WorldInfo;
+use crate::world::world::{BlockRaycast, World};
 use egui_winit::State;
 use glow::{Context, HasContext, NativeTexture, Program};
 use std::sync::Arc;
 use egui::{Color32, Stroke, TextureId};
-use glam::{IVec2, IVec3, Mat4, Vec3, Vec4};
+use glam::{IVec2, IVec3, Mat4, Vec2, Vec3, Vec4};
 use glutin::context::PossiblyCurrentContext;
-use glutin::surface::{GlSurface, Surface, WindowSurface};
+use glutin::surface::{GlSurface, Surface, SwapInterval, WindowSurface};
 use winit::dpi::PhysicalSize;
 use winit::event::{DeviceEvent, ElementState, MouseButton, MouseScrollDelta, WindowEvent};
 use winit::keyboard::KeyCode;
-use winit::window::{CursorGrabMode, Window};
-use worldgen::noise::perlin::PerlinNoise;
-use worldgen::noisemap::{NoiseMap, NoiseMapGenerator, NoiseMapGeneratorBase, Seed, Size, Step};
-use crate::world::chunk::CHUNK_DIMENSION;
-
-const POV: f32 = 90.0;
+use winit::window::{CursorGrabMode, Fullscreen, Window};
+#[cfg(feature = "gamepad")]
+use crate::gamepad;
+#[cfg(feature = "gamepad")]
+use crate::gamepad::{Gamepad, GamepadEvent};
+use crate::input::{Action, Binding};
+use crate::menu::{Menu, MenuAction, MenuScreen, SettingsMenu};
+use crate::settings::Settings;
+use crate::world::generator::WorldGenerator;
+
+const TICKS_PER_SECOND: f32 = 20.0;
+pub const TICK_TIME: f32 = 1.0 / TICKS_PER_SECOND;
+const MAX_FRAME_TIME: f32 = 0.25; // Longer hitches are dropped instead of simulated, so the game doesn't spiral catching up
+const CREATIVE_BREAK_DELAY: f32 = 0.25; // Holding Attack in creative breaks a block this often
+const SPAWN_SEARCH_RADIUS: i32 = 16; // Blocks around the world spawn searched for a safe place to (re)spawn
+const UNLOAD_MARGIN: i32 = 1; // Chunks are unloaded this many chunks beyond the render distance, so walking along a chunk border doesn't reload them every time
 
 const VERTEX_SHADER: &str = include_str!("shader/vertex.glsl");
 const FRAGMENT_SHADER: &str = include_str!("shader/fragment.glsl");
 const BLOCK_ATLAS: &[u8] = include_bytes!("../res/atlas/block_atlas.raw");
 const UI_ATLAS: &[u8] = include_bytes!("../res/atlas/ui_atlas.raw");
 
-#[derive(PartialEq)]
+#[derive(PartialEq, Clone, Copy)]
 enum Scene {
     Menu,
-    Game
+    Game,
+    Paused, // The world is still rendered, but nothing is simulated
+    Settings, // Opened from the pause menu
+    Chat, // The world keeps running while the player types
+    Dead // The death screen, the world keeps running without the player
+}
+
+/// Buttons of the pause menu, applied after the UI pass
+enum PauseAction {
+    Resume,
+    Settings,
+    SaveAndQuit,
+    Quit
+}
+
+/// Buttons of the death screen
+enum DeathAction {
+    Respawn,
+    SaveAndQuit
 }
 
 pub struct GameState {
@@ -42,18 +81,38 @@ pub struct GameState {
     player: Player,
     block_texture: NativeTexture,
     selected_hotbar_slot_index: u8,
-    keys_pressed: HashSet<KeyCode>,
+    pressed_bindings: HashSet<Binding>, // Keys and mouse buttons held down in the game
     egui_context: egui::Context,
     egui_painter: egui_glow::Painter,
     egui_state: State,
     egui_ui_atlas_id: TextureId,
     egui_block_atlas_id: TextureId,
     program: Option<Program>,
-    active_scene: Scene
+    active_scene: Scene,
+    mining_block: Option<IVec3>,
+    mining_progress: f32,
+    break_cooldown: f32, // Time until holding Attack breaks the next block in creative
+    tick_accumulator: f32,
+    menu: Menu,
+    settings_menu: SettingsMenu,
+    #[cfg(feature = "gamepad")]
+    gamepad: Gamepad,
+    world_info: Option<WorldInfo>, // Info of the world being played, None in the menu
+    world_dir: Option<PathBuf>, // Directory the world being played is saved in
+    settings: Settings,
+    generator: Option<Box<dyn WorldGenerator>>, // Generator of the world being played, for chunks that come into render distance
+    loaded_center: Option<IVec2>, // Chunk the player was in when chunks were last loaded and unloaded around them
+    debug_overlay: bool, // Toggled with the ToggleDebug action
+    chat: Chat,
+    commands: CommandRegistry,
+    frame_times: FrameTimes,
+    exit_requested: bool,
+    exit_save_failed: bool // Set once saving on exit failed, closing again quits without saving
 }
 
 impl GameState {
-    pub fn new(gl: Arc<Context>, gl_surface: Surface<WindowSurface>, gl_context: PossiblyCurrentContext, window: Arc<Window>, egui_context: egui::Context, mut egui_painter: egui_glow::Painter, egui_state: State) -> Self {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(gl: Arc<Context>, gl_surface: Surface<WindowSurface>, gl_context: PossiblyCurrentContext, window: Arc<Window>, egui_context: egui::Context, mut egui_painter: egui_glow::Painter, egui_state: State, settings: Settings) -> Self {
         let (block_texture, egui_block_atlas_is, egui_ui_atlas_id) = Self::load_textures(&gl, &mut egui_painter);
 
         let mut gamestate: GameState = Self {
@@ -65,18 +124,42 @@ impl GameState {
             player: Player::new(),
             block_texture,
             selected_hotbar_slot_index: 0,
-            keys_pressed: HashSet::new(),
+            pressed_bindings: HashSet::new(),
             egui_context,
             egui_painter,
             egui_state,
             egui_ui_atlas_id,
             egui_block_atlas_id: egui_block_atlas_is,
             program: None,
-            active_scene: Scene::Menu
+            active_scene: Scene::Menu,
+            mining_block: None,
+            mining_progress: 0.0,
+            break_cooldown: 0.0,
+            tick_accumulator: 0.0,
+            menu: Menu::new(),
+            settings_menu: SettingsMenu::new(),
+            #[cfg(feature = "gamepad")]
+            gamepad: Gamepad::new(),
+            world_info: None,
+            world_dir: None,
+            settings,
+            generator: None,
+            loaded_center: None,
+            debug_overlay: false,
+            chat: Chat::new(),
+            commands: CommandRegistry::default(),
+            frame_times: FrameTimes::new(),
+            exit_requested: false,
+            exit_save_failed: false
         };
 
         gamestate.init_shader_and_buffers();
-        gamestate.generate_world();
+        gamestate.apply_settings(None);
+
+        #[cfg(feature = "gamepad")]
+        if let Some(error) = gamestate.gamepad.take_error() {
+            gamestate.show_error(error);
+        }
 
         gamestate
     }
@@ -88,12 +171,12 @@ impl GameState {
 
         unsafe {
             let vertex_shader = self.gl.create_shader(glow::VERTEX_SHADER).unwrap();
-            self.gl.shader_source(vertex_shader, &vertex_shader_src);
+            self.gl.shader_source(vertex_shader, vertex_shader_src);
             self.gl.compile_shader(vertex_shader);
             assert!(self.gl.get_shader_compile_status(vertex_shader));
 
             let fragment_shader = self.gl.create_shader(glow::FRAGMENT_SHADER).unwrap();
-            self.gl.shader_source(fragment_shader, &fragment_shader_src);
+            self.gl.shader_source(fragment_shader, fragment_shader_src);
             self.gl.compile_shader(fragment_shader);
             assert!(self.gl.get_shader_compile_status(fragment_shader));
 
@@ -108,19 +191,242 @@ impl GameState {
         }
     }
 
-    pub fn generate_world(&mut self) {
-        let nm = NoiseMap::new(PerlinNoise::new()).set_seed(Seed::of("12345678910")).set_size(Size::of(CHUNK_DIMENSION as i64, CHUNK_DIMENSION as i64)).set_step(Step::of(0.0005, 0.0005));
+    fn get_player_chunk(&self) -> IVec2 {
+        let pos = self.player.get_pos();
+        IVec2::new(pos.x.floor() as i32, pos.z.floor() as i32).div_euclid(IVec2::splat(CHUNK_DIMENSION as i32))
+    }
+
+    /// Saves and unloads the chunks that are out of range of the player, then loads the ones that came into range
+    fn load_chunks_around_player(&mut self) {
+        let center = self.get_player_chunk();
 
-        const X_CHUNKS: i8 = 2;
-        const Y_CHUNKS: i8 = 2;
+        for mut chunk in self.world.remove_chunks_outside(center, self.settings.render_distance + UNLOAD_MARGIN) {
+            chunk.delete_buffers(&self.gl);
 
-        for x in -X_CHUNKS..X_CHUNKS {
-            for y in -X_CHUNKS..Y_CHUNKS {
-                self.world.insert_chunk(IVec2::new(x as i32, y as i32), self.program.unwrap(), nm.generate_chunk(x as i64, y as i64));
+            if let Some(dir) = &self.world_dir && let Err(error) = save::save_chunk(dir, &chunk) {
+                self.chat.add_message(error, true);
             }
         }
 
-        self.world.reload_world(&self.gl);
+        self.load_chunks_around(center);
+        self.loaded_center = Some(center);
+    }
+
+    /// Loads every chunk within the render distance of the `center` chunk that isn't loaded yet.
+    /// Chunks come from the save if they were saved there and from the generator otherwise.
+    fn load_chunks_around(&mut self, center: IVec2) {
+        let Some(generator) = &self.generator else {
+            return;
+        };
+
+        let distance = self.settings.render_distance;
+
+        for x in -distance..=distance {
+            for z in -distance..=distance {
+                let pos = center + IVec2::new(x, z);
+
+                if self.world.has_chunk(pos) {
+                    continue;
+                }
+
+                match self.world_dir.as_deref().and_then(|dir| save::load_chunk(dir, pos, self.program.unwrap())) {
+                    Some(chunk) => self.world.add_chunk(chunk),
+                    None => self.world.insert_chunk(pos, self.program.unwrap(), generator.as_ref())
+                }
+            }
+        }
+    }
+
+    fn create_world(&mut self, info: WorldInfo) -> Result<(), String> {
+        let generator = info.preset.create(info.seed, &info.generator_settings)?;
+
+        self.player = Player::new();
+        self.generator = Some(generator);
+        self.world_dir = Some(save::get_new_world_dir(&save::get_saves_dir(), &info.name));
+        self.world_info = Some(info);
+        self.choose_world_spawn();
+        self.player.set_pos(get_block_center(self.world.get_spawn()));
+        self.load_chunks_around_player();
+
+        // Saved right away, so the world is in the list even if the game crashes
+        self.save_world()?;
+
+        Ok(())
+    }
+
+    fn load_world(&mut self, dir: &Path) -> Result<(), String> {
+        let info = save::load_info(dir)?;
+        let generator = save::create_generator(dir, &info)?;
+
+        self.player = Player::new();
+        let has_player = save::load_player(dir, &mut self.player)?;
+        save::load_pending_writes(dir, &mut self.world);
+        self.generator = Some(generator);
+        self.world_dir = Some(dir.to_path_buf());
+
+        match info.spawn {
+            Some(spawn) => self.world.set_spawn(spawn),
+            None => self.choose_world_spawn() // Saved before worlds had a spawn
+        }
+
+        if !has_player {
+            self.player.set_pos(get_block_center(self.world.get_spawn()));
+        }
+
+        self.world_info = Some(info);
+        self.load_chunks_around_player();
+
+        Ok(())
+    }
+
+    /// Sets the world spawn to the safe surface closest to the origin, or above the generator's spawn height if there is none
+    fn choose_world_spawn(&mut self) {
+        self.load_chunks_around(IVec2::ZERO);
+
+        let fallback = IVec3::new(0, self.generator.as_ref().map_or(0, |generator| generator.get_spawn_height(0, 0)) + 1, 0);
+        let spawn = self.world.find_safe_spawn(IVec2::ZERO, SPAWN_SEARCH_RADIUS).unwrap_or(fallback);
+
+        self.world.set_spawn(spawn);
+    }
+
+    /// Brings the player back at their spawn point. If it is blocked, the player respawns at the safe place closest to the world spawn instead.
+    fn respawn(&mut self) {
+        let world_spawn = self.world.get_spawn();
+
+        if let Some(spawn_point) = self.player.get_spawn_point() {
+            self.load_chunks_around(IVec2::new(spawn_point.x, spawn_point.z).div_euclid(IVec2::splat(CHUNK_DIMENSION as i32)));
+
+            if !self.world.is_safe_spawn(spawn_point) {
+                self.chat.add_message("Your spawn point is blocked".to_string(), true);
+                self.player.set_spawn_point(None);
+            }
+        }
+
+        let pos = match self.player.get_spawn_point() {
+            Some(spawn_point) => spawn_point,
+            None => {
+                self.load_chunks_around(IVec2::new(world_spawn.x, world_spawn.z).div_euclid(IVec2::splat(CHUNK_DIMENSION as i32)));
+                self.world.find_safe_spawn(IVec2::new(world_spawn.x, world_spawn.z), SPAWN_SEARCH_RADIUS).unwrap_or(world_spawn)
+            }
+        };
+
+        self.player.respawn(get_block_center(pos));
+        self.set_scene(Scene::Game);
+    }
+
+    /// Saves the world being played, if there is one
+    fn save_world(&mut self) -> Result<(), String> {
+        let (Some(info), Some(dir)) = (&mut self.world_info, &self.world_dir) else {
+            return Ok(());
+        };
+
+        info.last_played = save::get_time();
+        info.spawn = Some(self.world.get_spawn());
+
+        save::save_world(dir, info, &self.world, &self.player)
+    }
+
+    fn unload_world(&mut self) {
+        self.world.delete_buffers(&self.gl);
+        self.world = World::new();
+        self.chat.clear();
+        self.world_info = None;
+        self.world_dir = None;
+        self.generator = None;
+        self.loaded_center = None;
+    }
+
+    /// Saves the world and the settings before the game closes.
+    /// Returns false the first time saving fails, so the error can be read before closing again
+    pub fn on_exit(&mut self) -> bool {
+        let result = self.save_world().and(self.settings.save());
+
+        match result {
+            Err(error) if !self.exit_save_failed => {
+                self.exit_save_failed = true;
+                self.exit_requested = false;
+                self.show_error(format!("{error}, close the game again to quit without saving"));
+                false
+            }
+            _ => true
+        }
+    }
+
+    /// Applies the settings that differ from `previous`, or all of them without it
+    fn apply_settings(&mut self, previous: Option<&Settings>) {
+        let settings = &self.settings;
+
+        if previous.is_none_or(|previous| previous.gui_scale != settings.gui_scale) {
+            self.egui_context.set_zoom_factor(self.settings.gui_scale);
+        }
+
+        if previous.is_none_or(|previous| previous.fullscreen != settings.fullscreen) {
+            self.window.set_fullscreen(self.settings.fullscreen.then_some(Fullscreen::Borderless(None)));
+        }
+
+        if previous.is_none_or(|previous| previous.vsync != settings.vsync) {
+            let interval = if self.settings.vsync {SwapInterval::Wait(NonZeroU32::MIN)} else {SwapInterval::DontWait};
+
+            if let Err(error) = self.gl_surface.set_swap_interval(&self.gl_context, interval) {
+                self.show_error(format!("Failed to change vsync: {error}"));
+            }
+        }
+
+        if previous.is_none_or(|previous| previous.render_distance != self.settings.render_distance) {
+            self.load_chunks_around_player();
+        }
+    }
+
+    /// Shows an error on the menu screen, or in the chat while a world is open
+    fn show_error(&mut self, error: String) {
+        if self.active_scene == Scene::Menu {
+            self.menu.set_error(error);
+        } else {
+            self.chat.add_message(error, true);
+        }
+    }
+
+    fn quit_to_title(&mut self) {
+        if let Err(error) = self.save_world() {
+            self.chat.add_message(error, true);
+            return;
+        }
+
+        self.unload_world();
+        self.menu.open(MenuScreen::Title);
+        self.set_scene(Scene::Menu);
+    }
+
+    /// Set once the player chose to quit the game, the event loop exits after the current frame
+    pub fn is_exit_requested(&self) -> bool {self.exit_requested}
+
+    fn set_scene(&mut self, scene: Scene) {
+        if scene != Scene::Game {
+            // Keys released while a menu is open never arrive, so forget them
+            self.pressed_bindings.clear();
+            self.mining_block = None;
+            self.mining_progress = 0.0;
+        }
+
+        self.settings_menu = SettingsMenu::new();
+
+        self.active_scene = scene;
+        self.update_cursor_grab(true);
+    }
+
+    /// The cursor is grabbed and hidden only while playing in a focused window
+    fn update_cursor_grab(&self, focused: bool) {
+        if self.active_scene == Scene::Game && focused {
+            if let Err(_e) = self.window.set_cursor_grab(CursorGrabMode::Confined) {
+                //Retry because on X11 grabbing cursor is blocked while tabbing back in
+                std::thread::sleep(std::time::Duration::from_millis(100));
+                self.window.set_cursor_grab(CursorGrabMode::Confined).expect("Failed to grab cursor");
+            }
+            self.window.set_cursor_visible(false);
+        } else {
+            self.window.set_cursor_grab(CursorGrabMode::None).expect("Failed to release cursor");
+            self.window.set_cursor_visible(true);
+        }
     }
 
     fn load_textures(gl: &Context, egui_painter: &mut egui_glow::Painter) -> (NativeTexture, TextureId, TextureId) {
@@ -188,12 +494,111 @@ impl GameState {
             gl.tex_parameter_i32(glow::TEXTURE_2D, glow::TEXTURE_WRAP_S, glow::CLAMP_TO_EDGE as i32);
             gl.tex_parameter_i32(glow::TEXTURE_2D, glow::TEXTURE_WRAP_T, glow::CLAMP_TO_EDGE as i32);
 
-            return (block_texture, egui_painter.register_native_texture(block_texture), egui_painter.register_native_texture(ui_texture));
-        };
+            (block_texture, egui_painter.register_native_texture(block_texture), egui_painter.register_native_texture(ui_texture))
+        }
     }
 
     pub fn new_frame(&mut self, delta_time: f32) {
-        self.player.update_pos(delta_time, self.keys_pressed.clone(), &self.world)
+        self.frame_times.record(delta_time);
+
+        #[cfg(feature = "gamepad")]
+        self.update_gamepad(delta_time);
+
+        if !matches!(self.active_scene, Scene::Game | Scene::Chat | Scene::Dead) {
+            return;
+        }
+
+        self.tick_accumulator += delta_time.min(MAX_FRAME_TIME);
+
+        while self.tick_accumulator >= TICK_TIME {
+            self.tick_accumulator -= TICK_TIME;
+            self.tick();
+        }
+
+        if self.loaded_center.is_some_and(|center| center != self.get_player_chunk()) {
+            self.load_chunks_around_player();
+        }
+
+        self.world.rebuild_dirty_chunks(&self.gl.clone());
+
+        if self.player.is_dead() && self.active_scene != Scene::Dead {
+            self.set_scene(Scene::Dead);
+        }
+    }
+
+    /// One fixed simulation step, everything that changes the game runs here independent of the frame rate
+    fn tick(&mut self) {
+        if !self.player.is_dead() {
+            self.player.update_pos(TICK_TIME, &self.get_active_actions(), self.get_movement_stick(), &self.world);
+            self.update_mining(TICK_TIME);
+        }
+
+        self.world.tick();
+    }
+
+    fn update_mining(&mut self, delta_time: f32) {
+        let can_mine = self.is_action_active(Action::Attack) && self.player.get_game_mode() != GameMode::Spectator;
+        let target = if can_mine {self.get_targeted_block()} else {None};
+
+        let Some(hit) = target else {
+            self.mining_block = None;
+            self.mining_progress = 0.0;
+            self.break_cooldown = 0.0;
+            return;
+        };
+
+        if self.player.get_game_mode() == GameMode::Creative {
+            self.break_cooldown -= delta_time;
+
+            // Blocks break instantly without drops or tool wear
+            if self.break_cooldown <= 0.0 {
+                self.world.set_block(hit.block_pos, block::AIR, &self.gl.clone());
+                self.break_cooldown = CREATIVE_BREAK_DELAY;
+            }
+
+            return;
+        }
+
+        if self.mining_block != Some(hit.block_pos) {
+            self.mining_block = Some(hit.block_pos);
+            self.mining_progress = 0.0;
+        }
+
+        let block_id = self.world.get_global_block(hit.block_pos);
+        let tool = self.get_selected_tool();
+
+        self.mining_progress += delta_time / item::get_break_time(block_id, tool.as_ref());
+
+        if self.mining_progress >= 1.0 {
+            self.break_block(hit.block_pos, block_id, tool);
+        }
+    }
+
+    fn break_block(&mut self, pos: IVec3, block_id: u8, tool: Option<Tool>) {
+        let slot = self.selected_hotbar_slot_index as usize;
+        let inventory = self.player.get_inventory_mut();
+
+        let drop = block::get_properties(block_id).drop;
+
+        if drop != block::AIR && item::can_harvest(block_id, tool.as_ref()) {
+            inventory.add_item(Item::Block(drop));
+        }
+
+        if let Some(ItemStack {item: Item::Tool(tool), ..}) = inventory.get_slot_mut(slot) && tool.wear() {
+            inventory.clear_slot(slot);
+        }
+
+        self.world.set_block(pos, block::AIR, &self.gl.clone());
+
+        self.mining_block = None;
+        self.mining_progress = 0.0;
+    }
+
+    fn get_selected_tool(&self) -> Option<Tool> {
+        match self.player.get_inventory().get_slot(self.selected_hotbar_slot_index as usize) {
+            Some(ItemStack {item: Item::Tool(tool), ..}) => Some(*tool),
+            _ => None
+        }
     }
 
     pub fn render(&mut self) {
@@ -202,7 +607,7 @@ impl GameState {
                 self.render_menu();
             }
 
-            Scene::Game => {
+            Scene::Game | Scene::Paused | Scene::Settings | Scene::Chat | Scene::Dead => {
                 self.render_game();
             }
         }
@@ -210,25 +615,17 @@ impl GameState {
 
     fn render_menu(&mut self) {
         let raw_input = self.egui_state.take_egui_input(&self.window);
+        let previous_settings = self.settings.clone();
+        let mut action = None;
 
         let full_output = self.egui_context.run_ui(raw_input, |ctx| {
-            egui::CentralPanel::default().show(ctx, |ui| {
-                ui.vertical_centered(|ui| {
-                    ui.add_space(ui.available_height() * 0.1);
-
-                    ui.heading(egui::RichText::new("Brickbyte").size(100.0).strong().color(Color32::from_rgb(255, 255, 255)));
-
-                    ui.add_space(250.0);
-
-                    if ui.add_sized([250.0, 60.0], egui::Button::new(egui::RichText::new("Start").size(24.0))).clicked() {
-                        self.window.set_cursor_grab(CursorGrabMode::Confined).expect("Failed to grab cursor");
-                        self.window.set_cursor_visible(false);
-                        self.active_scene = Scene::Game;
-                    }
-                });
+            egui::CentralPanel::default().show_inside(ctx, |ui| {
+                action = self.menu.show(ui, &mut self.settings, &mut self.settings_menu);
             });
         });
 
+        self.apply_settings(Some(&previous_settings));
+
         self.egui_state.handle_platform_output(&self.window, full_output.platform_output);
 
         let primitives = self.egui_context.tessellate(full_output.shapes, full_output.pixels_per_point);
@@ -240,11 +637,32 @@ impl GameState {
         }
 
         self.gl_surface.swap_buffers(&self.gl_context).expect("Unable to swap buffers");
+
+        let result = match action {
+            Some(MenuAction::Play(dir)) => self.load_world(&dir),
+            Some(MenuAction::Create {name, seed, preset, settings}) => self.create_world(WorldInfo::new(&name, seed, preset, settings)),
+            Some(MenuAction::Quit) => {
+                self.exit_requested = true;
+                return;
+            }
+            None => return
+        };
+
+        match result {
+            Ok(()) => self.set_scene(Scene::Game),
+            Err(error) => {
+                self.unload_world();
+                self.menu.set_error(error);
+            }
+        }
     }
 
     fn render_game(&mut self) {
-        let projection = Mat4::perspective_rh_gl(POV.to_radians(), self.window.inner_size().width as f32 / self.window.inner_size().height as f32, 0.1, 200.0);
-        let view = Mat4::look_at_rh(self.player.get_head_pos(), self.player.get_head_pos() + self.player.get_camera_front(), Vec3::Y);
+        let far = (200.0f32).max(((self.settings.render_distance + 1) * CHUNK_DIMENSION as i32) as f32 * 1.5); // Far enough to see the corners of the render distance
+        let projection = Mat4::perspective_rh_gl(self.settings.fov.to_radians(), self.window.inner_size().width as f32 / self.window.inner_size().height as f32, 0.1, far);
+        let camera_pos = self.player.get_interpolated_head_pos(self.tick_accumulator / TICK_TIME);
+        let player_chunk = IVec2::new(camera_pos.x.floor() as i32, camera_pos.z.floor() as i32).div_euclid(IVec2::splat(CHUNK_DIMENSION as i32));
+        let view = Mat4::look_at_rh(camera_pos, camera_pos + self.player.get_camera_front(), Vec3::Y);
         let pv = projection * view;
 
         unsafe {
@@ -252,10 +670,14 @@ impl GameState {
             self.gl.disable(glow::SCISSOR_TEST);
             self.gl.disable(glow::BLEND);
 
-            self.gl.clear_color(0.5, 0.7, 0.9, 1.0);
+            if self.player.is_underwater() {
+                self.gl.clear_color(0.1, 0.25, 0.6, 1.0);
+            } else {
+                self.gl.clear_color(0.5, 0.7, 0.9, 1.0);
+            }
             self.gl.clear(glow::COLOR_BUFFER_BIT | glow::DEPTH_BUFFER_BIT);
 
-            self.world.render_world(&self.gl, pv, Some(self.block_texture));
+            self.world.render_world(&self.gl, pv, Some(self.block_texture), self.program.unwrap(), player_chunk, self.settings.render_distance);
         }
 
         let raw_input = self.egui_state.take_egui_input(&self.window);
@@ -264,6 +686,11 @@ impl GameState {
         let painter_layer = self.egui_context.layer_painter(egui::LayerId::new(egui::Order::Foreground, egui::Id::new("UI")));
         let center = self.egui_context.content_rect().center();
 
+        //Underwater tint
+        if self.player.is_underwater() {
+            painter_layer.rect_filled(self.egui_context.content_rect(), 0.0, Color32::from_rgba_unmultiplied(20, 60, 170, 90));
+        }
+
         //Crosshair
         painter_layer.line_segment([center - egui::vec2(10.0, 0.0), center + egui::vec2(10.0, 0.0)], Stroke::new(2.0, Color32::GRAY));
         painter_layer.line_segment([center - egui::vec2(0.0, 10.0), center + egui::vec2(0.0, 10.0)], Stroke::new(2.0, Color32::GRAY));
@@ -283,24 +710,52 @@ impl GameState {
 
         painter_layer.rect(egui::Rect::from_two_pos(center + egui::vec2(-HOTBAR_WIDTH / 2.0 + (self.selected_hotbar_slot_index as f32 * HOTBAR_SLOT_WIDTH), center.y - HOTBAR_HEIGHT), center + egui::vec2(-((HOTBAR_WIDTH / 2.0) - (HOTBAR_SLOT_WIDTH + LINE_THICKNESS as f32)) + (self.selected_hotbar_slot_index as f32 * HOTBAR_SLOT_WIDTH), center.y - HOTBAR_POS_HEIGHT)), 2.0, Color32::TRANSPARENT, Stroke::new(LINE_THICKNESS, Color32::WHITE), egui::StrokeKind::Inside);
 
-        const TEX_SIZE: f64 = 256.0;
-        const PIXEL_MARGIN: f64 = 0.1 / TEX_SIZE; //Margin is needed because otherwise the textures are not cropped correctly
+        for i in 0..HOTBAR_SIZE {
+            let Some(stack) = self.player.get_inventory().get_slot(i) else { continue; };
 
-        for i in 0..5 {
             let rect = egui::Rect::from_two_pos(
                 center + egui::vec2(-245.0 + (i as f32 * 55.0), center.y - 65.0),
                 center + egui::vec2(-195.0 + (i as f32 * 55.0), center.y - 15.0)
             );
 
-            let uv = egui::Rect::from_min_max(
-                egui::pos2((i as f32 * 16.0 + 0.1) / TEX_SIZE as f32, 0.0 + PIXEL_MARGIN as f32),
-                egui::pos2(((i + 1) as f32 * 16.0 - 0.1) / TEX_SIZE as f32, 0.0625 - PIXEL_MARGIN as f32)
-            );
+            match stack.item {
+                Item::Block(id) => {
+                    painter_layer.image(self.egui_block_atlas_id, rect, Self::get_atlas_uv((id - 1) % 16, (id - 1) / 16), Color32::WHITE);
+                }
+
+                Item::Tool(tool) => {
+                    painter_layer.image(self.egui_ui_atlas_id, rect, Self::get_atlas_uv(tool.tier as u8, tool.kind as u8 + 1), Color32::WHITE);
+
+                    let max_durability = tool.tier.get_max_durability();
+
+                    if tool.get_durability() < max_durability {
+                        let ratio = tool.get_durability() as f32 / max_durability as f32;
+                        let bar_color = Color32::from_rgb(((1.0 - ratio) * 255.0) as u8, (ratio * 255.0) as u8, 0);
 
-            painter_layer.image(self.egui_block_atlas_id, rect, uv, Color32::WHITE);
+                        painter_layer.rect_filled(egui::Rect::from_min_size(rect.left_bottom() + egui::vec2(4.0, -6.0), egui::vec2(rect.width() - 8.0, 3.0)), 0.0, Color32::BLACK);
+                        painter_layer.rect_filled(egui::Rect::from_min_size(rect.left_bottom() + egui::vec2(4.0, -6.0), egui::vec2((rect.width() - 8.0) * ratio, 3.0)), 0.0, bar_color);
+                    }
+                }
+            }
+
+            if stack.count > 1 {
+                painter_layer.text(rect.right_bottom(), egui::Align2::RIGHT_BOTTOM, stack.count.to_string(), egui::FontId::proportional(16.0), Color32::WHITE);
+            }
         }
 
-        for i in 0..self.player.get_health() {
+        if let Some(stack) = self.player.get_inventory().get_slot(self.selected_hotbar_slot_index as usize) {
+            painter_layer.text(center + egui::vec2(0.0, center.y - HOTBAR_HEIGHT - 10.0), egui::Align2::CENTER_BOTTOM, stack.item.get_name(), egui::FontId::proportional(18.0), Color32::WHITE);
+        }
+
+        //Mining progress
+        if self.mining_progress > 0.0 {
+            painter_layer.rect_filled(egui::Rect::from_min_size(center + egui::vec2(-25.0, 20.0), egui::vec2(50.0, 5.0)), 1.0, Color32::from_black_alpha(150));
+            painter_layer.rect_filled(egui::Rect::from_min_size(center + egui::vec2(-25.0, 20.0), egui::vec2(50.0 * self.mining_progress.min(1.0), 5.0)), 1.0, Color32::WHITE);
+        }
+
+        let health = if self.player.get_game_mode() == GameMode::Survival {self.player.get_health()} else {0}; // Only survival players can be hurt
+
+        for i in 0..health {
             let rect = egui::Rect::from_two_pos(
                 center + egui::vec2(-255.0 + (i as f32 * 35.0), center.y - 115.0),
                 center + egui::vec2(-205.0 + (i as f32 * 35.0), center.y - 65.0)
@@ -314,6 +769,42 @@ impl GameState {
             painter_layer.image(self.egui_ui_atlas_id, rect, uv, Color32::WHITE);
         }
 
+        //Breath
+        if self.player.get_breath() < 1.0 && self.player.get_game_mode() == GameMode::Survival {
+            let bubbles = (self.player.get_breath() * 8.0).ceil() as u8;
+
+            for i in 0..bubbles {
+                let rect = egui::Rect::from_two_pos(
+                    center + egui::vec2(-255.0 + (i as f32 * 35.0), center.y - 155.0),
+                    center + egui::vec2(-205.0 + (i as f32 * 35.0), center.y - 105.0)
+                );
+
+                painter_layer.image(self.egui_ui_atlas_id, rect, Self::get_atlas_uv(1, 0), Color32::WHITE);
+            }
+        }
+
+        if self.debug_overlay {
+            self.render_debug_overlay();
+        }
+
+        let chat_action = if self.active_scene == Scene::Chat {
+            self.chat.show(&self.egui_context, &self.commands)
+        } else {
+            self.chat.show_recent(&self.egui_context);
+            None
+        };
+
+        let death_action = if self.active_scene == Scene::Dead {self.render_death_screen()} else {None};
+
+        let pause_action = match self.active_scene {
+            Scene::Paused => self.render_pause_menu(),
+            Scene::Settings => {
+                self.render_settings();
+                None
+            }
+            _ => None
+        };
+
         let full_output = self.egui_context.end_pass();
 
         self.egui_state.handle_platform_output(&self.window, full_output.platform_output);
@@ -327,118 +818,441 @@ impl GameState {
         }
 
         self.gl_surface.swap_buffers(&self.gl_context).expect("Unable to swap buffers");
+
+        match pause_action {
+            Some(PauseAction::Resume) => self.set_scene(Scene::Game),
+            Some(PauseAction::Settings) => self.set_scene(Scene::Settings),
+            Some(PauseAction::SaveAndQuit) => self.quit_to_title(),
+            Some(PauseAction::Quit) => self.exit_requested = true,
+            None => {}
+        }
+
+        match death_action {
+            Some(DeathAction::Respawn) => self.respawn(),
+            Some(DeathAction::SaveAndQuit) => self.quit_to_title(),
+            None => {}
+        }
+
+        match chat_action {
+            Some(ChatAction::Send(text)) => self.send_chat(&text),
+            Some(ChatAction::Close) => self.set_scene(Scene::Game),
+            None => {}
+        }
+    }
+
+    /// Runs a line starting with `/` as a command and shows anything else as a message
+    fn send_chat(&mut self, text: &str) {
+        self.set_scene(Scene::Game);
+
+        if !text.starts_with('/') {
+            self.chat.add_message(text.to_string(), false);
+            return;
+        }
+
+        let mut context = CommandContext {world: &mut self.world, player: &mut self.player, seed: self.world_info.as_ref().map(|info| info.seed)};
+
+        match self.commands.execute(text, &mut context) {
+            Ok(message) => self.chat.add_message(message, false),
+            Err(error) => self.chat.add_message(error, true)
+        }
     }
 
-    pub fn window_resized(&self, size: PhysicalSize<u32>) {
+    /// Position, chunk and performance info in the top left corner
+    fn render_debug_overlay(&self) {
+        const GRAPH_SIZE: egui::Vec2 = egui::vec2(debug::FRAME_HISTORY as f32, 60.0);
+        const GRAPH_MIN_TIME: f32 = 1.0 / 30.0; // The graph's height covers at least this frame time
+
+        let ctx = self.egui_context.clone();
+        let pos = self.player.get_pos();
+        let block_pos = pos.floor().as_ivec3();
+        let chunk_pos = IVec2::new(block_pos.x, block_pos.z).div_euclid(IVec2::splat(CHUNK_DIMENSION as i32));
+        let (yaw, pitch) = self.player.get_rotation();
+        let head_pos = self.player.get_head_pos().floor().as_ivec3();
+        let vertex_count: usize = self.world.get_chunks().map(|chunk| chunk.get_vertex_count()).sum();
+        let chunk_vertex_count = self.world.get_chunks().find(|chunk| chunk.position == chunk_pos).map_or(0, |chunk| chunk.get_vertex_count());
+
+        let targeted_block = match self.get_targeted_block() {
+            Some(hit) => {
+                let block_pos = hit.block_pos;
+                format!("{} at {} {} {}", block::get_properties(self.world.get_global_block(block_pos)).name, block_pos.x, block_pos.y, block_pos.z)
+            }
+            None => "none".to_string()
+        };
+
+        let lines = [
+            format!("{:.0} fps ({:.2} ms)", self.frame_times.get_fps(), 1000.0 / self.frame_times.get_fps().max(1.0)),
+            format!("XYZ: {:.3} / {:.3} / {:.3}", pos.x, pos.y, pos.z),
+            format!("Block: {} {} {}", block_pos.x, block_pos.y, block_pos.z),
+            format!("Chunk: {} {} (in chunk {} {})", chunk_pos.x, chunk_pos.y, block_pos.x.rem_euclid(CHUNK_DIMENSION as i32), block_pos.z.rem_euclid(CHUNK_DIMENSION as i32)),
+            format!("Facing: {} ({yaw:.1} / {pitch:.1})", debug::get_facing(yaw)),
+            format!("Targeted block: {targeted_block}"),
+            format!("Light: {}", self.world.get_light(head_pos)),
+            format!("Game mode: {}{}", self.player.get_game_mode().get_name(), if self.player.is_flying() {" (flying)"} else {""}),
+            format!("Loaded chunks: {}", self.world.get_chunks().count()),
+            format!("Vertices: {vertex_count} ({chunk_vertex_count} in this chunk)")
+        ];
+
+        egui::Area::new(egui::Id::new("Debug")).anchor(egui::Align2::LEFT_TOP, egui::vec2(5.0, 5.0)).interactable(false).show(&ctx, |ui| {
+            egui::Frame::new().fill(Color32::from_black_alpha(150)).inner_margin(5.0).show(ui, |ui| {
+                for line in &lines {
+                    ui.label(egui::RichText::new(line).monospace().color(Color32::WHITE));
+                }
+
+                ui.add_space(5.0);
+
+                // Frame-time graph, newest frame on the right
+                let (rect, _) = ui.allocate_exact_size(GRAPH_SIZE, egui::Sense::hover());
+                let painter = ui.painter_at(rect);
+                let scale = GRAPH_SIZE.y / self.frame_times.get_max().max(GRAPH_MIN_TIME);
+                let offset = debug::FRAME_HISTORY - self.frame_times.get_times().len();
+
+                painter.rect_filled(rect, 0.0, Color32::from_black_alpha(100));
+
+                for (i, time) in self.frame_times.get_times().iter().enumerate() {
+                    let x = rect.left() + (offset + i) as f32;
+                    let color = if *time > GRAPH_MIN_TIME {Color32::RED} else if *time > 1.0 / 60.0 {Color32::YELLOW} else {Color32::GREEN};
+
+                    painter.line_segment([egui::pos2(x, rect.bottom()), egui::pos2(x, rect.bottom() - time * scale)], Stroke::new(1.0, color));
+                }
+
+                let target_y = rect.bottom() - scale / 60.0;
+                painter.line_segment([egui::pos2(rect.left(), target_y), egui::pos2(rect.right(), target_y)], Stroke::new(1.0, Color32::from_white_alpha(80)));
+            });
+        });
+    }
+
+    /// Darkens the game behind a menu
+    fn dim_background(&self) {
+        let painter = self.egui_context.layer_painter(egui::LayerId::new(egui::Order::Middle, egui::Id::new("Dim")));
+        painter.rect_filled(self.egui_context.content_rect(), 0.0, Color32::from_black_alpha(150));
+    }
+
+    fn render_pause_menu(&self) -> Option<PauseAction> {
+        let ctx = self.egui_context.clone();
+        let mut action = None;
+
+        self.dim_background();
+
+        egui::Area::new(egui::Id::new("Pause")).anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO).show(&ctx, |ui| {
+            ui.vertical_centered(|ui| {
+                ui.label(egui::RichText::new("Game Paused").size(32.0).color(Color32::WHITE));
+                ui.add_space(20.0);
+
+                let buttons = [("Resume", PauseAction::Resume), ("Settings", PauseAction::Settings), ("Save and quit to title", PauseAction::SaveAndQuit), ("Quit game", PauseAction::Quit)];
+
+                for (text, button_action) in buttons {
+                    if ui.add_sized([250.0, 40.0], egui::Button::new(egui::RichText::new(text).size(18.0))).clicked() {
+                        action = Some(button_action);
+                    }
+                }
+            });
+        });
+
+        action
+    }
+
+    fn render_death_screen(&self) -> Option<DeathAction> {
+        let ctx = self.egui_context.clone();
+        let mut action = None;
+
+        let painter = self.egui_context.layer_painter(egui::LayerId::new(egui::Order::Middle, egui::Id::new("Dim")));
+        painter.rect_filled(self.egui_context.content_rect(), 0.0, Color32::from_rgba_unmultiplied(120, 0, 0, 120));
+
+        egui::Area::new(egui::Id::new("Death")).anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO).show(&ctx, |ui| {
+            ui.vertical_centered(|ui| {
+                ui.label(egui::RichText::new("You died!").size(40.0).color(Color32::WHITE));
+                ui.add_space(20.0);
+
+                let buttons = [("Respawn", DeathAction::Respawn), ("Save and quit to title", DeathAction::SaveAndQuit)];
+
+                for (text, button_action) in buttons {
+                    if ui.add_sized([250.0, 40.0], egui::Button::new(egui::RichText::new(text).size(18.0))).clicked() {
+                        action = Some(button_action);
+                    }
+                }
+            });
+        });
+
+        action
+    }
+
+    fn render_settings(&mut self) {
+        let ctx = self.egui_context.clone();
+        let previous_settings = self.settings.clone();
+        let mut done = false;
+
+        self.dim_background();
+
+        egui::Area::new(egui::Id::new("Settings")).anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO).show(&ctx, |ui| {
+            ui.vertical_centered(|ui| {
+                done = self.settings_menu.show(ui, &mut self.settings);
+            });
+        });
+
+        self.apply_settings(Some(&previous_settings));
+
+        if done {
+            self.close_settings();
+        }
+    }
+
+    /// Saves the settings and goes back to the pause menu
+    fn close_settings(&mut self) {
+        if let Err(error) = self.settings.save() {
+            self.chat.add_message(error, true);
+        }
+
+        self.set_scene(Scene::Paused);
+    }
+
+    fn get_atlas_uv(tile_x: u8, tile_y: u8) -> egui::Rect {
+        const TEX_SIZE: f64 = 256.0;
+        const PIXEL_MARGIN: f64 = 0.1 / TEX_SIZE; //Margin is needed because otherwise the textures are not cropped correctly
+
+        egui::Rect::from_min_max(
+            egui::pos2((tile_x as f32 * 16.0 + 0.1) / TEX_SIZE as f32, tile_y as f32 * 0.0625 + PIXEL_MARGIN as f32),
+            egui::pos2(((tile_x + 1) as f32 * 16.0 - 0.1) / TEX_SIZE as f32, (tile_y + 1) as f32 * 0.0625 - PIXEL_MARGIN as f32)
+        )
+    }
+
+    pub fn window_resized(&mut self, size: PhysicalSize<u32>) {
+        if !self.settings.fullscreen && size.width > 0 && size.height > 0 {
+            self.settings.window_width = size.width;
+            self.settings.window_height = size.height;
+        }
+
         self.gl_surface.resize(&self.gl_context, NonZeroU32::new(size.width.max(1)).unwrap(), NonZeroU32::new(size.height.max(1)).unwrap());
         unsafe {self.gl.viewport(0, 0, size.width as i32, size.height as i32);}
     }
 
     pub fn window_event(&mut self, event: &WindowEvent) {
-        let _ = self.egui_state.on_window_event(&self.window, &event);
+        let _ = self.egui_state.on_window_event(&self.window, event);
     }
 
-    pub fn focused(&self, focused: bool) {
-        if self.active_scene == Scene::Game {
-            if focused{
-                if let Err(_e) = self.window.set_cursor_grab(CursorGrabMode::Confined) {
-                    //Retry because on X11 grabbing cursor is blocked while tabbing back in
-                    std::thread::sleep(std::time::Duration::from_millis(100));
-                    self.window.set_cursor_grab(CursorGrabMode::Confined).expect("Failed to grab cursor");
-                }
-                self.window.set_cursor_visible(false);
-            }
-            else if !focused{
-                self.window.set_cursor_grab(CursorGrabMode::None).expect("Failed to release cursor");
-                self.window.set_cursor_visible(true);
-            }
+    pub fn focused(&mut self, focused: bool) {
+        // Tabbing out pauses the game instead of letting it run in the background
+        if !focused && self.active_scene == Scene::Game {
+            self.set_scene(Scene::Paused);
         }
+
+        self.update_cursor_grab(focused);
     }
 
     pub fn keyboard_input(&mut self, state: ElementState, key_code: KeyCode) {
+        if state == ElementState::Pressed && self.settings_menu.bind(&mut self.settings, Binding::Key(key_code)) {
+            return;
+        }
+
+        // The chat is typed into, so its keys aren't actions and Escape closes it like other text fields
+        if self.active_scene == Scene::Chat {
+            if state == ElementState::Pressed && key_code == KeyCode::Escape {
+                self.set_scene(Scene::Game);
+            }
+
+            return;
+        }
+
+        self.binding_input(Binding::Key(key_code), state);
+    }
+
+    pub fn mouse_button_input(&mut self, state: ElementState, button: MouseButton) {
+        if state == ElementState::Pressed && self.settings_menu.bind(&mut self.settings, Binding::Mouse(button)) {
+            return;
+        }
+
+        self.binding_input(Binding::Mouse(button), state);
+    }
+
+    fn binding_input(&mut self, binding: Binding, state: ElementState) {
+        if self.active_scene != Scene::Game {
+            if state == ElementState::Pressed {
+                for action in self.settings.key_bindings.get_actions(binding).collect::<Vec<_>>() {
+                    self.trigger_menu_action(action);
+                }
+            }
+
+            return;
+        }
+
         match state {
             ElementState::Pressed => {
-                self.keys_pressed.insert(key_code);
+                // Held keys repeat their press event, but an item is only used once per press
+                if self.pressed_bindings.insert(binding) {
+                    let actions: Vec<Action> = self.settings.key_bindings.get_actions(binding).collect();
+
+                    for action in actions {
+                        self.trigger_action(action);
+                    }
+                }
             }
 
             ElementState::Released => {
-                self.keys_pressed.remove(&key_code);
+                self.pressed_bindings.remove(&binding);
             }
         }
     }
 
-    pub fn mouse_button_input(&mut self, state: ElementState, button: MouseButton) {
-        if state == ElementState::Pressed {
-            let size = self.window.inner_size();
+    /// Actions held on the keyboard, mouse or gamepad
+    fn get_active_actions(&self) -> HashSet<Action> {
+        let actions = self.settings.key_bindings.get_active_actions(&self.pressed_bindings);
+
+        #[cfg(feature = "gamepad")]
+        let actions = actions.union(self.gamepad.get_actions()).copied().collect();
 
-            let projection = Mat4::perspective_rh_gl(POV.to_radians(), size.width as f32 / size.height as f32, 0.1, 100.0);
-            let view = Mat4::look_at_rh(self.player.get_head_pos(), self.player.get_head_pos() + self.player.get_camera_front(), Vec3::Y);
+        actions
+    }
 
-            let ndc = Vec4::new((2.0 * (size.width as f32 / 2.0)) / size.width as f32 - 1.0, 1.0 - (2.0 * (size.height as f32 / 2.0)) / size.height as f32, -1.0, 1.0);
+    /// Analog movement from the gamepad's left stick, zero without one
+    fn get_movement_stick(&self) -> Vec2 {
+        #[cfg(feature = "gamepad")]
+        return self.gamepad.get_movement();
+
+        #[cfg(not(feature = "gamepad"))]
+        Vec2::ZERO
+    }
 
-            let mut eye = projection.inverse() * ndc;
-            eye = Vec4::new(eye.x, eye.y, -1.0, 0.0);
+    fn is_action_active(&self, action: Action) -> bool {
+        self.get_active_actions().contains(&action)
+    }
 
-            let world_ray = view.inverse() * eye;
-            let ray_dir = Vec3::new(world_ray.x, world_ray.y, world_ray.z).normalize();
+    #[cfg(feature = "gamepad")]
+    fn update_gamepad(&mut self, delta_time: f32) {
+        for event in self.gamepad.update(self.settings.gamepad_dead_zone) {
+            match (event, self.active_scene) {
+                (GamepadEvent::Pause, Scene::Game) => self.set_scene(Scene::Paused),
+                (GamepadEvent::Pause, Scene::Paused) => self.set_scene(Scene::Game),
+                (GamepadEvent::Action(action), Scene::Game) => self.trigger_action(action),
+                _ => {}
+            }
+        }
 
-            let ray_origin = self.player.get_head_pos();
+        if self.active_scene == Scene::Game {
+            let look = self.gamepad.get_look() * gamepad::LOOK_SPEED * delta_time;
+            self.player.update_rotation((look.x as f64, -look.y as f64), self.settings.gamepad_sensitivity);
+        }
+    }
 
-            let hit = {
-                self.world.raycast_block(ray_origin, ray_dir, 10.0)
-            };
+    fn use_item(&mut self) {
+        if self.player.get_game_mode() == GameMode::Spectator {
+            return;
+        }
 
-            if let Some(hit) = hit {
-                match button {
-                    MouseButton::Left => {
-                        self.world.set_block(hit.block_pos, 0, &self.gl.clone());
-                    }
+        let Some(hit) = self.get_targeted_block() else {
+            return;
+        };
 
-                    MouseButton::Right => {
-                        let block_pos = hit.prev_block_pos;
-                        let player_pos: IVec3 = IVec3::new(self.player.get_pos().x.floor() as i32, self.player.get_pos().y.ceil() as i32, self.player.get_pos().z.floor() as i32);
-                        let player_head_pos: IVec3 = IVec3::new(self.player.get_head_pos().x.floor() as i32, self.player.get_head_pos().y.floor() as i32, self.player.get_head_pos().z.floor() as i32);
+        let slot = self.selected_hotbar_slot_index as usize;
 
-                        if block_pos != player_pos && block_pos != player_head_pos {
-                            self.world.set_block(hit.prev_block_pos, self.selected_hotbar_slot_index + 1, &self.gl.clone());
-                        }
-                    },
+        if let Some(ItemStack {item: Item::Block(block_id), ..}) = self.player.get_inventory().get_slot(slot).copied() && !self.player.get_aabb().intersects_block(hit.prev_block_pos) {
+            self.world.set_block(hit.prev_block_pos, block_id, &self.gl.clone());
 
-                    _ => ()
-                }
+            // Blocks are infinite in creative
+            if self.player.get_game_mode() == GameMode::Survival {
+                self.player.get_inventory_mut().take_one(slot);
             }
         }
     }
 
+    /// Selects the hotbar slot holding the targeted block, or puts the block into the hotbar. Only works in creative.
+    fn pick_block(&mut self) {
+        if self.player.get_game_mode() != GameMode::Creative {
+            return;
+        }
+
+        let Some(hit) = self.get_targeted_block() else {
+            return;
+        };
+
+        let item = Item::Block(self.world.get_global_block(hit.block_pos));
+        let inventory = self.player.get_inventory_mut();
+
+        let slot = (0..HOTBAR_SIZE).find(|&i| inventory.get_slot(i).is_some_and(|stack| stack.item == item))
+            .or((0..HOTBAR_SIZE).find(|&i| inventory.get_slot(i).is_none()))
+            .unwrap_or(self.selected_hotbar_slot_index as usize);
+
+        if inventory.get_slot(slot).is_none_or(|stack| stack.item != item) {
+            inventory.set_slot(slot, Some(ItemStack::new(item, 1)));
+        }
+
+        self.selected_hotbar_slot_index = slot as u8;
+    }
+
+    fn get_targeted_block(&self) -> Option<BlockRaycast> {
+        let size = self.window.inner_size();
+
+        let projection = Mat4::perspective_rh_gl(self.settings.fov.to_radians(), size.width as f32 / size.height as f32, 0.1, 100.0);
+        let camera_pos = self.player.get_interpolated_head_pos(self.tick_accumulator / TICK_TIME);
+        let view = Mat4::look_at_rh(camera_pos, camera_pos + self.player.get_camera_front(), Vec3::Y);
+
+        let ndc = Vec4::new((2.0 * (size.width as f32 / 2.0)) / size.width as f32 - 1.0, 1.0 - (2.0 * (size.height as f32 / 2.0)) / size.height as f32, -1.0, 1.0);
+
+        let mut eye = projection.inverse() * ndc;
+        eye = Vec4::new(eye.x, eye.y, -1.0, 0.0);
+
+        let world_ray = view.inverse() * eye;
+        let ray_dir = Vec3::new(world_ray.x, world_ray.y, world_ray.z).normalize();
+
+        // Cast from the rendered camera, so the highlight and the block that gets broken or placed are always the same
+        self.world.raycast_block(camera_pos, ray_dir, 10.0)
+    }
+
     pub fn mouse_motion_input(&mut self, _delta: (f64, f64), event: DeviceEvent) {
-        if let DeviceEvent::MouseMotion {delta} = event {
-            self.player.update_rotation(delta);
+        if let DeviceEvent::MouseMotion {delta} = event && self.active_scene == Scene::Game {
+            self.player.update_rotation(delta, self.settings.mouse_sensitivity);
         }
     }
 
     pub fn mouse_wheel_input(&mut self, delta: MouseScrollDelta) {
-        let mut delta_index: i8 = 0;
+        let binding = match delta {
+            MouseScrollDelta::LineDelta(_, y) if y >= 1.0 => Binding::ScrollUp,
+            MouseScrollDelta::LineDelta(_, y) if y <= -1.0 => Binding::ScrollDown,
+            MouseScrollDelta::PixelDelta(pos) if pos.y >= 10.0 => Binding::ScrollUp,
+            MouseScrollDelta::PixelDelta(pos) if pos.y <= -10.0 => Binding::ScrollDown,
+            _ => return
+        };
 
-        match delta {
-            MouseScrollDelta::LineDelta(_, y) => {
-                if y >= 1.0 {delta_index = -1;}
-                else if y <= -1.0 {delta_index = 1;}
-            }
-            MouseScrollDelta::PixelDelta(pos) => {
-                if pos.y >= 10.0 {delta_index = -1;}
-                else if pos.y <= -10.0 {delta_index = 1;}
-            }
+        if self.settings_menu.bind(&mut self.settings, binding) {
+            return;
         }
 
-        if delta_index != 0 {
-            let new_index = self.selected_hotbar_slot_index as i8 + delta_index;
-            if new_index < 0 {
-                self.selected_hotbar_slot_index = 8;
-            } else if new_index > 8 {
-                self.selected_hotbar_slot_index = 0;
-            } else {
-                self.selected_hotbar_slot_index = new_index as u8;
+        self.binding_input(binding, ElementState::Pressed);
+        self.binding_input(binding, ElementState::Released);
+    }
+
+    /// Runs the actions that happen once per press
+    fn trigger_action(&mut self, action: Action) {
+        match action {
+            Action::UseItem => self.use_item(),
+            Action::PickBlock => self.pick_block(),
+            Action::Hotbar(slot) => self.selected_hotbar_slot_index = slot,
+            Action::HotbarNext => self.scroll_hotbar(1),
+            Action::HotbarPrevious => self.scroll_hotbar(-1),
+            Action::Pause => self.set_scene(Scene::Paused),
+            Action::ToggleDebug => self.debug_overlay = !self.debug_overlay,
+            Action::OpenChat | Action::OpenCommand => {
+                self.chat.open(if action == Action::OpenCommand {"/"} else {""});
+                self.set_scene(Scene::Chat);
             }
+            _ => {}
+        }
+    }
+
+    /// Runs the actions that also work while a screen is open
+    fn trigger_menu_action(&mut self, action: Action) {
+        match (action, self.active_scene) {
+            (Action::Pause, Scene::Paused) => self.set_scene(Scene::Game),
+            (Action::Pause, Scene::Settings) => self.close_settings(),
+            (Action::ToggleDebug, Scene::Paused | Scene::Settings | Scene::Dead) => self.debug_overlay = !self.debug_overlay,
+            _ => {}
         }
     }
+
+    fn scroll_hotbar(&mut self, delta_index: i8) {
+        self.selected_hotbar_slot_index = (self.selected_hotbar_slot_index as i8 + delta_index).rem_euclid(HOTBAR_SIZE as i8) as u8;
+    }
+}
+
+/// Where the player stands in the block at `pos`
+fn get_block_center(pos: IVec3) -> Vec3 {
+    pos.as_vec3() + Vec3::new(0.5, 0.0, 0.5)
 }
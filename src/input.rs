@@ -0,0 +1,280 @@
+// SPDX-FileCopyrightText: © 2025 - 2026 Elias Steininger <elias.st4600@gmail.com> and Project Contributors (see CONTRIBUTORS.md)
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use crate::world::item::HOTBAR_SIZE;
+use std::collections::{HashMap, HashSet};
+use winit::event::MouseButton;
+use winit::keyboard::KeyCode;
+
+/// Keys that can be bound, only these are found again when the bindings are loaded
+const BINDABLE_KEYS: [KeyCode; 85] = [
+    KeyCode::KeyA, KeyCode::KeyB, KeyCode::KeyC, KeyCode::KeyD, KeyCode::KeyE, KeyCode::KeyF, KeyCode::KeyG, KeyCode::KeyH, KeyCode::KeyI,
+    KeyCode::KeyJ, KeyCode::KeyK, KeyCode::KeyL, KeyCode::KeyM, KeyCode::KeyN, KeyCode::KeyO, KeyCode::KeyP, KeyCode::KeyQ, KeyCode::KeyR,
+    KeyCode::KeyS, KeyCode::KeyT, KeyCode::KeyU, KeyCode::KeyV, KeyCode::KeyW, KeyCode::KeyX, KeyCode::KeyY, KeyCode::KeyZ,
+    KeyCode::Digit0, KeyCode::Digit1, KeyCode::Digit2, KeyCode::Digit3, KeyCode::Digit4, KeyCode::Digit5, KeyCode::Digit6, KeyCode::Digit7, KeyCode::Digit8, KeyCode::Digit9,
+    KeyCode::Escape, KeyCode::F1, KeyCode::F2, KeyCode::F3, KeyCode::F4, KeyCode::F5, KeyCode::F6, KeyCode::F7, KeyCode::F8, KeyCode::F9, KeyCode::F10, KeyCode::F11, KeyCode::F12,
+    KeyCode::Space, KeyCode::Tab, KeyCode::CapsLock, KeyCode::Enter, KeyCode::Backspace,
+    KeyCode::ShiftLeft, KeyCode::ShiftRight, KeyCode::ControlLeft, KeyCode::ControlRight, KeyCode::AltLeft, KeyCode::AltRight,
+    KeyCode::ArrowUp, KeyCode::ArrowDown, KeyCode::ArrowLeft, KeyCode::ArrowRight,
+    KeyCode::Backquote, KeyCode::Minus, KeyCode::Equal, KeyCode::BracketLeft, KeyCode::BracketRight, KeyCode::Backslash,
+    KeyCode::Semicolon, KeyCode::Quote, KeyCode::Comma, KeyCode::Period, KeyCode::Slash,
+    KeyCode::Numpad0, KeyCode::Numpad1, KeyCode::Numpad2, KeyCode::Numpad3, KeyCode::Numpad4, KeyCode::Numpad5, KeyCode::Numpad6, KeyCode::Numpad7, KeyCode::Numpad8, KeyCode::Numpad9
+];
+
+const BINDABLE_MOUSE_BUTTONS: [MouseButton; 5] = [MouseButton::Left, MouseButton::Right, MouseButton::Middle, MouseButton::Back, MouseButton::Forward];
+
+const HOTBAR_KEYS: [KeyCode; HOTBAR_SIZE] = [KeyCode::Digit1, KeyCode::Digit2, KeyCode::Digit3, KeyCode::Digit4, KeyCode::Digit5, KeyCode::Digit6, KeyCode::Digit7, KeyCode::Digit8, KeyCode::Digit9];
+const HOTBAR_NAMES: [&str; HOTBAR_SIZE] = ["Hotbar slot 1", "Hotbar slot 2", "Hotbar slot 3", "Hotbar slot 4", "Hotbar slot 5", "Hotbar slot 6", "Hotbar slot 7", "Hotbar slot 8", "Hotbar slot 9"];
+const HOTBAR_CONFIG_KEYS: [&str; HOTBAR_SIZE] = ["bind_hotbar_1", "bind_hotbar_2", "bind_hotbar_3", "bind_hotbar_4", "bind_hotbar_5", "bind_hotbar_6", "bind_hotbar_7", "bind_hotbar_8", "bind_hotbar_9"];
+
+/// Something the player can do, the game asks for actions instead of keys
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Action {
+    MoveForward,
+    MoveBackward,
+    MoveLeft,
+    MoveRight,
+    Jump,
+    Sprint,
+    Sneak,
+    Attack, // Mines the targeted block while held
+    UseItem, // Places the held block
+    PickBlock, // Puts the targeted block in the hotbar, only in creative
+    Hotbar(u8), // Selects the hotbar slot with this index
+    HotbarNext,
+    HotbarPrevious,
+    Pause, // Also leaves the pause and settings screens
+    ToggleDebug, // Shows or hides the debug overlay
+    OpenChat,
+    OpenCommand // Opens the chat with a `/` typed
+}
+
+impl Action {
+    pub const ALL: [Action; 25] = [
+        Action::MoveForward, Action::MoveBackward, Action::MoveLeft, Action::MoveRight, Action::Jump, Action::Sprint, Action::Sneak, Action::Attack, Action::UseItem, Action::PickBlock,
+        Action::Hotbar(0), Action::Hotbar(1), Action::Hotbar(2), Action::Hotbar(3), Action::Hotbar(4), Action::Hotbar(5), Action::Hotbar(6), Action::Hotbar(7), Action::Hotbar(8),
+        Action::HotbarNext, Action::HotbarPrevious, Action::Pause, Action::ToggleDebug, Action::OpenChat, Action::OpenCommand
+    ];
+
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            Action::MoveForward => "Move forward",
+            Action::MoveBackward => "Move backward",
+            Action::MoveLeft => "Strafe left",
+            Action::MoveRight => "Strafe right",
+            Action::Jump => "Jump",
+            Action::Sprint => "Sprint",
+            Action::Sneak => "Sneak",
+            Action::Attack => "Attack / Mine",
+            Action::UseItem => "Use item / Place",
+            Action::PickBlock => "Pick block",
+            Action::Hotbar(slot) => HOTBAR_NAMES[*slot as usize],
+            Action::HotbarNext => "Next hotbar slot",
+            Action::HotbarPrevious => "Previous hotbar slot",
+            Action::Pause => "Pause",
+            Action::ToggleDebug => "Debug overlay",
+            Action::OpenChat => "Open chat",
+            Action::OpenCommand => "Open command"
+        }
+    }
+
+    /// Key of the action in the config file
+    fn get_config_key(&self) -> &'static str {
+        match self {
+            Action::MoveForward => "bind_move_forward",
+            Action::MoveBackward => "bind_move_backward",
+            Action::MoveLeft => "bind_move_left",
+            Action::MoveRight => "bind_move_right",
+            Action::Jump => "bind_jump",
+            Action::Sprint => "bind_sprint",
+            Action::Sneak => "bind_sneak",
+            Action::Attack => "bind_attack",
+            Action::UseItem => "bind_use_item",
+            Action::PickBlock => "bind_pick_block",
+            Action::Hotbar(slot) => HOTBAR_CONFIG_KEYS[*slot as usize],
+            Action::HotbarNext => "bind_hotbar_next",
+            Action::HotbarPrevious => "bind_hotbar_previous",
+            Action::Pause => "bind_pause",
+            Action::ToggleDebug => "bind_toggle_debug",
+            Action::OpenChat => "bind_open_chat",
+            Action::OpenCommand => "bind_open_command"
+        }
+    }
+
+    fn get_default_binding(&self) -> Binding {
+        match self {
+            Action::MoveForward => Binding::Key(KeyCode::KeyW),
+            Action::MoveBackward => Binding::Key(KeyCode::KeyS),
+            Action::MoveLeft => Binding::Key(KeyCode::KeyA),
+            Action::MoveRight => Binding::Key(KeyCode::KeyD),
+            Action::Jump => Binding::Key(KeyCode::Space),
+            Action::Sprint => Binding::Key(KeyCode::ShiftLeft),
+            Action::Sneak => Binding::Key(KeyCode::ControlLeft),
+            Action::Attack => Binding::Mouse(MouseButton::Left),
+            Action::UseItem => Binding::Mouse(MouseButton::Right),
+            Action::PickBlock => Binding::Mouse(MouseButton::Middle),
+            Action::Hotbar(slot) => Binding::Key(HOTBAR_KEYS[*slot as usize]),
+            Action::HotbarNext => Binding::ScrollDown,
+            Action::HotbarPrevious => Binding::ScrollUp,
+            Action::Pause => Binding::Key(KeyCode::Escape),
+            Action::ToggleDebug => Binding::Key(KeyCode::F3),
+            Action::OpenChat => Binding::Key(KeyCode::KeyT),
+            Action::OpenCommand => Binding::Key(KeyCode::Slash)
+        }
+    }
+}
+
+/// A key, mouse button or scroll direction an action is bound to
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Binding {
+    Key(KeyCode),
+    Mouse(MouseButton),
+    ScrollUp, // A scroll wheel step is pressed and released at once
+    ScrollDown
+}
+
+impl Binding {
+    /// Whether the binding can be saved, every other input is ignored while rebinding
+    pub fn is_bindable(&self) -> bool {
+        match self {
+            Binding::Key(key) => BINDABLE_KEYS.contains(key),
+            Binding::Mouse(button) => BINDABLE_MOUSE_BUTTONS.contains(button),
+            Binding::ScrollUp | Binding::ScrollDown => true
+        }
+    }
+
+    /// Name in the config file, like `KeyW` or `MouseLeft`
+    fn to_text(self) -> String {
+        match self {
+            Binding::Key(key) => format!("{key:?}"),
+            Binding::Mouse(button) => format!("Mouse{button:?}"),
+            Binding::ScrollUp => "ScrollUp".to_string(),
+            Binding::ScrollDown => "ScrollDown".to_string()
+        }
+    }
+
+    fn from_text(text: &str) -> Option<Binding> {
+        BINDABLE_KEYS.iter().map(|&key| Binding::Key(key))
+            .chain(BINDABLE_MOUSE_BUTTONS.iter().map(|&button| Binding::Mouse(button)))
+            .chain([Binding::ScrollUp, Binding::ScrollDown])
+            .find(|binding| binding.to_text() == text)
+    }
+
+    /// Name shown in the controls menu
+    pub fn get_name(&self) -> String {
+        match self {
+            Binding::Key(key) => {
+                let name = format!("{key:?}");
+                name.strip_prefix("Key").or(name.strip_prefix("Digit")).unwrap_or(&name).to_string()
+            }
+            Binding::Mouse(button) => format!("{button:?} Mouse"),
+            Binding::ScrollUp => "Scroll up".to_string(),
+            Binding::ScrollDown => "Scroll down".to_string()
+        }
+    }
+}
+
+/// The binding of every action
+#[derive(Clone, PartialEq)]
+pub struct KeyBindings {
+    bindings: HashMap<Action, Binding>
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        KeyBindings {
+            bindings: Action::ALL.iter().map(|&action| (action, action.get_default_binding())).collect()
+        }
+    }
+}
+
+impl KeyBindings {
+    pub fn get(&self, action: Action) -> Binding {self.bindings[&action]}
+
+    pub fn set(&mut self, action: Action, binding: Binding) {
+        self.bindings.insert(action, binding);
+    }
+
+    /// Actions whose binding is held down
+    pub fn get_active_actions(&self, pressed: &HashSet<Binding>) -> HashSet<Action> {
+        Action::ALL.into_iter().filter(|action| pressed.contains(&self.get(*action))).collect()
+    }
+
+    /// Actions triggered by pressing the binding
+    pub fn get_actions(&self, binding: Binding) -> impl Iterator<Item = Action> + '_ {
+        Action::ALL.into_iter().filter(move |action| self.get(*action) == binding)
+    }
+
+    /// Whether another action is bound to the same input
+    pub fn has_conflict(&self, action: Action) -> bool {
+        self.get_actions(self.get(action)).count() > 1
+    }
+
+    pub fn to_text(&self) -> String {
+        Action::ALL.iter().map(|action| format!("{} = {}\n", action.get_config_key(), self.get(*action).to_text())).collect()
+    }
+
+    /// Reads the bindings from config values, unknown bindings keep their default
+    pub fn from_values(values: &HashMap<String, String>) -> Self {
+        let mut key_bindings = KeyBindings::default();
+
+        for action in Action::ALL {
+            if let Some(binding) = values.get(action.get_config_key()).and_then(|text| Binding::from_text(text)) {
+                key_bindings.set(action, binding);
+            }
+        }
+
+        key_bindings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bindings_round_trip_through_text() {
+        let mut key_bindings = KeyBindings::default();
+        key_bindings.set(Action::Jump, Binding::Mouse(MouseButton::Middle));
+        key_bindings.set(Action::MoveForward, Binding::Key(KeyCode::ArrowUp));
+
+        let values = crate::world::save::parse_key_values(&key_bindings.to_text());
+        assert!(KeyBindings::from_values(&values) == key_bindings);
+
+        let values = crate::world::save::parse_key_values("bind_jump = KeyNotAKey\nbind_sneak = Digit4");
+        let loaded = KeyBindings::from_values(&values);
+        assert_eq!(loaded.get(Action::Jump), Binding::Key(KeyCode::Space));
+        assert_eq!(loaded.get(Action::Sneak), Binding::Key(KeyCode::Digit4));
+    }
+
+    #[test]
+    fn pressed_bindings_activate_their_actions() {
+        let mut key_bindings = KeyBindings::default();
+        key_bindings.set(Action::Sprint, Binding::Key(KeyCode::KeyW));
+
+        let pressed = HashSet::from([Binding::Key(KeyCode::KeyW), Binding::Mouse(MouseButton::Left)]);
+
+        assert_eq!(key_bindings.get_active_actions(&pressed), HashSet::from([Action::MoveForward, Action::Sprint, Action::Attack]));
+        assert!(key_bindings.has_conflict(Action::Sprint));
+        assert!(!key_bindings.has_conflict(Action::Jump));
+        assert_eq!(Binding::Key(KeyCode::Digit4).get_name(), "4");
+    }
+
+    #[test]
+    fn hotbar_actions_have_their_own_bindings() {
+        let key_bindings = KeyBindings::default();
+
+        assert_eq!(key_bindings.get_actions(Binding::Key(KeyCode::Digit1)).collect::<Vec<_>>(), vec![Action::Hotbar(0)]);
+        assert_eq!(key_bindings.get_actions(Binding::Key(KeyCode::Digit9)).collect::<Vec<_>>(), vec![Action::Hotbar(8)]);
+        assert_eq!(key_bindings.get_actions(Binding::ScrollDown).collect::<Vec<_>>(), vec![Action::HotbarNext]);
+        assert_eq!(key_bindings.get_actions(Binding::Key(KeyCode::Escape)).collect::<Vec<_>>(), vec![Action::Pause]);
+        assert_eq!(key_bindings.get_actions(Binding::Key(KeyCode::Slash)).collect::<Vec<_>>(), vec![Action::OpenCommand]);
+        assert!(Action::ALL.iter().all(|action| !key_bindings.has_conflict(*action)));
+
+        let values = crate::world::save::parse_key_values("bind_hotbar_3 = KeyQ\nbind_hotbar_previous = ScrollDown");
+        let loaded = KeyBindings::from_values(&values);
+        assert_eq!(loaded.get(Action::Hotbar(2)), Binding::Key(KeyCode::KeyQ));
+        assert_eq!(loaded.get(Action::HotbarPrevious), Binding::ScrollDown);
+        assert!(loaded.has_conflict(Action::HotbarNext));
+    }
+}
@@ -4,8 +4,17 @@
 #![windows_subsystem = "windows"]
 mod world;
 mod gamestate;
+mod debug;
+#[cfg(feature = "gamepad")]
+mod gamepad;
+mod input;
+mod command;
+mod chat;
+mod menu;
+mod settings;
 
 use crate::gamestate::GameState;
+use crate::settings::Settings;
 use glow::{Context};
 use glutin::config::{ConfigSurfaceTypes, ConfigTemplateBuilder};
 use glutin::context::{ContextApi, ContextAttributesBuilder, NotCurrentGlContext, PossiblyCurrentContext};
@@ -17,9 +26,9 @@ use std::sync::Arc;
 use std::time::Instant;
 use egui_winit::State;
 use winit::dpi::PhysicalSize;
-use winit::event::{DeviceEvent, ElementState, KeyEvent, WindowEvent};
+use winit::event::{DeviceEvent, KeyEvent, WindowEvent};
 use winit::event_loop::{ActiveEventLoop, EventLoopBuilder};
-use winit::keyboard::{KeyCode, PhysicalKey};
+use winit::keyboard::PhysicalKey;
 use winit::raw_window_handle::{HasDisplayHandle, HasWindowHandle};
 use winit::window::{Window, WindowId};
 
@@ -78,13 +87,14 @@ impl Brickbyte {
 
 impl winit::application::ApplicationHandler for Brickbyte {
     fn resumed(&mut self, event_loop: &ActiveEventLoop) {
-        let window: Arc<Window> = Arc::new(event_loop.create_window(Window::default_attributes().with_title("Brickbyte").with_inner_size(PhysicalSize::new(1200.0, 800.0))).unwrap());
+        let settings = Settings::load();
+        let window: Arc<Window> = Arc::new(event_loop.create_window(Window::default_attributes().with_title("Brickbyte").with_inner_size(PhysicalSize::new(settings.window_width, settings.window_height))).unwrap());
 
         let (gl_surface, gl_context, gl, egui_context, egui_painter, egui_state) = self.init_gl(&window);
 
         self.window = Some(window.clone());
 
-        let game = GameState::new(gl, gl_surface, gl_context, window, egui_context, egui_painter, egui_state);
+        let game = GameState::new(gl, gl_surface, gl_context, window, egui_context, egui_painter, egui_state, settings);
 
         self.gamestate = Some(game);
     }
@@ -94,19 +104,15 @@ impl winit::application::ApplicationHandler for Brickbyte {
         self.gamestate.as_mut().unwrap().window_event(&event);
 
         match event {
-            WindowEvent::CloseRequested => {
+            WindowEvent::CloseRequested if self.gamestate.as_mut().unwrap().on_exit() => {
                 event_loop.exit();
             }
 
             WindowEvent::Resized(size) => {
-                self.gamestate.as_ref().unwrap().window_resized(size);
+                self.gamestate.as_mut().unwrap().window_resized(size);
             }
 
             WindowEvent::KeyboardInput {event: KeyEvent {physical_key: PhysicalKey::Code(key_code), state, ..}, ..} => {
-                if state == ElementState::Pressed && key_code == KeyCode::Backspace {
-                    event_loop.exit();
-                }
-
                 self.gamestate.as_mut().unwrap().keyboard_input(state, key_code);
             }
 
@@ -119,7 +125,7 @@ impl winit::application::ApplicationHandler for Brickbyte {
             }
 
             WindowEvent::Focused(focused) => {
-                self.gamestate.as_ref().unwrap().focused(focused);
+                self.gamestate.as_mut().unwrap().focused(focused);
             }
 
             WindowEvent::RedrawRequested => {
@@ -136,12 +142,16 @@ impl winit::application::ApplicationHandler for Brickbyte {
         }
     }
 
-    fn about_to_wait(&mut self, _event_loop: &ActiveEventLoop) {
+    fn about_to_wait(&mut self, event_loop: &ActiveEventLoop) {
         let now = Instant::now();
         let delta_time = now.duration_since(self.last_update).as_secs_f32();
         self.last_update = now;
         self.gamestate.as_mut().unwrap().new_frame(delta_time);
 
+        if self.gamestate.as_ref().unwrap().is_exit_requested() && self.gamestate.as_mut().unwrap().on_exit() {
+            event_loop.exit();
+        }
+
         if let Some(window) = &self.window {
             window.request_redraw();
         }
@@ -0,0 +1,456 @@
+// SPDX-FileCopyrightText: © 2025 - 2026 Elias Steininger <elias.st4600@gmail.com> and Project Contributors (see CONTRIBUTORS.md)
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use crate::input::{Action, Binding, KeyBindings};
+use crate::settings;
+use crate::settings::Settings;
+use crate::world::generator::{GeneratorPreset, GeneratorSettings};
+use crate::world::save;
+use crate::world::save::WorldInfo;
+use egui::{Color32, RichText};
+use std::path::PathBuf;
+use winit::keyboard::KeyCode;
+
+const DEFAULT_WORLD_NAME: &str = "New World";
+const BUTTON_SIZE: [f32; 2] = [250.0, 40.0];
+const SMALL_BUTTON_SIZE: [f32; 2] = [120.0, 30.0];
+const LIST_WIDTH: f32 = 500.0;
+const LIST_HEIGHT: f32 = 300.0;
+
+#[derive(PartialEq, Clone, Copy)]
+pub enum MenuScreen {
+    Title,
+    WorldList,
+    CreateWorld,
+    Settings
+}
+
+/// Change to the selected world that waits for the player to confirm it
+enum WorldEdit {
+    Rename(String),
+    Delete
+}
+
+/// Choices that need the game, applied by `GameState` after the UI pass
+pub enum MenuAction {
+    Play(PathBuf),
+    Create {name: String, seed: u64, preset: GeneratorPreset, settings: GeneratorSettings},
+    Quit
+}
+
+/// Title screen, world list and world creation
+pub struct Menu {
+    screen: MenuScreen,
+    worlds: Vec<(PathBuf, WorldInfo)>, // Saved worlds, the most recently played first
+    selected_world: Option<usize>,
+    world_edit: Option<WorldEdit>,
+    name_input: String,
+    seed_input: String, // Empty for a random seed
+    generator_preset: GeneratorPreset,
+    generator_settings: GeneratorSettings,
+    error: Option<String>
+}
+
+impl Menu {
+    pub fn new() -> Self {
+        Menu {
+            screen: MenuScreen::Title,
+            worlds: Vec::new(),
+            selected_world: None,
+            world_edit: None,
+            name_input: String::new(),
+            seed_input: String::new(),
+            generator_preset: GeneratorPreset::Default,
+            generator_settings: GeneratorSettings::default(),
+            error: None
+        }
+    }
+
+    pub fn open(&mut self, screen: MenuScreen) {
+        match screen {
+            MenuScreen::WorldList => self.refresh_worlds(),
+            MenuScreen::CreateWorld => {
+                self.name_input = DEFAULT_WORLD_NAME.to_string();
+                self.seed_input.clear();
+                self.generator_preset = GeneratorPreset::Default;
+                self.generator_settings = GeneratorSettings::default();
+            }
+            MenuScreen::Title | MenuScreen::Settings => {}
+        }
+
+        self.error = None;
+        self.screen = screen;
+    }
+
+    pub fn set_error(&mut self, error: String) {
+        self.error = Some(error);
+    }
+
+    fn refresh_worlds(&mut self) {
+        self.worlds = save::list_worlds(&save::get_saves_dir());
+        self.selected_world = None;
+        self.world_edit = None;
+    }
+
+    pub fn show(&mut self, ui: &mut egui::Ui, settings: &mut Settings, settings_menu: &mut SettingsMenu) -> Option<MenuAction> {
+        ui.vertical_centered(|ui| {
+            ui.add_space(ui.available_height() * 0.1);
+            ui.heading(RichText::new("Brickbyte").size(100.0).strong().color(Color32::WHITE));
+            ui.add_space(80.0);
+
+            let action = match self.screen {
+                MenuScreen::Title => self.show_title(ui),
+                MenuScreen::WorldList => self.show_world_list(ui),
+                MenuScreen::CreateWorld => self.show_create_world(ui),
+                MenuScreen::Settings => {
+                    if settings_menu.show(ui, settings) {
+                        self.open(MenuScreen::Title);
+
+                        if let Err(error) = settings.save() {
+                            self.set_error(error);
+                        }
+                    }
+
+                    None
+                }
+            };
+
+            if let Some(error) = &self.error {
+                ui.add_space(10.0);
+                ui.colored_label(Color32::from_rgb(255, 80, 80), error);
+            }
+
+            action
+        }).inner
+    }
+
+    fn show_title(&mut self, ui: &mut egui::Ui) -> Option<MenuAction> {
+        if ui.add_sized([250.0, 60.0], egui::Button::new(RichText::new("Singleplayer").size(24.0))).clicked() {
+            self.open(MenuScreen::WorldList);
+        }
+
+        if ui.add_sized([250.0, 60.0], egui::Button::new(RichText::new("Settings").size(24.0))).clicked() {
+            self.open(MenuScreen::Settings);
+        }
+
+        if ui.add_sized([250.0, 60.0], egui::Button::new(RichText::new("Quit").size(24.0))).clicked() {
+            return Some(MenuAction::Quit);
+        }
+
+        None
+    }
+
+    fn show_world_list(&mut self, ui: &mut egui::Ui) -> Option<MenuAction> {
+        let mut action = None;
+
+        ui.label(RichText::new("Select World").size(24.0).color(Color32::WHITE));
+        ui.add_space(10.0);
+
+        egui::Frame::group(ui.style()).show(ui, |ui| {
+            ui.set_width(LIST_WIDTH);
+            ui.set_height(LIST_HEIGHT);
+
+            if self.worlds.is_empty() {
+                ui.label("No worlds yet");
+            }
+
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                for (i, (dir, info)) in self.worlds.iter().enumerate() {
+                    let text = format!("{}\n{}  |  Seed {}  |  {}", info.name, save::format_time(info.last_played), info.seed, info.preset.get_name());
+                    let response = ui.add_sized([LIST_WIDTH, 40.0], egui::Button::selectable(self.selected_world == Some(i), text));
+
+                    if response.clicked() {
+                        self.selected_world = Some(i);
+                        self.world_edit = None;
+                    }
+
+                    if response.double_clicked() {
+                        action = Some(MenuAction::Play(dir.clone()));
+                    }
+                }
+            });
+        });
+
+        ui.add_space(10.0);
+
+        let mut confirmed = false;
+        let mut canceled = false;
+
+        match &mut self.world_edit {
+            Some(WorldEdit::Rename(name)) => {
+                ui.horizontal(|ui| {
+                    ui.add_space((ui.available_width() - LIST_WIDTH) / 2.0);
+                    ui.label("Name");
+                    ui.text_edit_singleline(name);
+                    confirmed = ui.button("Save").clicked();
+                    canceled = ui.button("Cancel").clicked();
+                });
+            }
+
+            Some(WorldEdit::Delete) => {
+                ui.label(format!("'{}' will be lost forever!", self.worlds[self.selected_world?].1.name));
+
+                ui.horizontal(|ui| {
+                    ui.add_space((ui.available_width() - SMALL_BUTTON_SIZE[0] * 2.0) / 2.0);
+                    confirmed = ui.add_sized(SMALL_BUTTON_SIZE, egui::Button::new("Delete")).clicked();
+                    canceled = ui.add_sized(SMALL_BUTTON_SIZE, egui::Button::new("Cancel")).clicked();
+                });
+            }
+
+            None => {
+                let selected = self.selected_world.map(|i| self.worlds[i].0.clone());
+
+                ui.horizontal(|ui| {
+                    ui.add_space((ui.available_width() - SMALL_BUTTON_SIZE[0] * 4.0) / 2.0);
+
+                    if ui.add_enabled(selected.is_some(), egui::Button::new("Play").min_size(SMALL_BUTTON_SIZE.into())).clicked() {
+                        action = selected.clone().map(MenuAction::Play);
+                    }
+
+                    if ui.add_enabled(selected.is_some(), egui::Button::new("Rename").min_size(SMALL_BUTTON_SIZE.into())).clicked() {
+                        self.world_edit = self.selected_world.map(|i| WorldEdit::Rename(self.worlds[i].1.name.clone()));
+                    }
+
+                    if ui.add_enabled(selected.is_some(), egui::Button::new("Duplicate").min_size(SMALL_BUTTON_SIZE.into())).clicked() && let Some(dir) = &selected {
+                        match save::duplicate_world(dir, &save::get_saves_dir()) {
+                            Ok(_) => self.refresh_worlds(),
+                            Err(error) => self.error = Some(error)
+                        }
+                    }
+
+                    if ui.add_enabled(selected.is_some(), egui::Button::new("Delete").min_size(SMALL_BUTTON_SIZE.into())).clicked() {
+                        self.world_edit = Some(WorldEdit::Delete);
+                    }
+                });
+            }
+        }
+
+        if confirmed {
+            self.apply_world_edit();
+        } else if canceled {
+            self.world_edit = None;
+        }
+
+        ui.add_space(20.0);
+
+        if ui.add_sized(BUTTON_SIZE, egui::Button::new(RichText::new("Create New World").size(18.0))).clicked() {
+            self.open(MenuScreen::CreateWorld);
+        }
+
+        if ui.add_sized(BUTTON_SIZE, egui::Button::new(RichText::new("Back").size(18.0))).clicked() {
+            self.open(MenuScreen::Title);
+        }
+
+        action
+    }
+
+    fn apply_world_edit(&mut self) {
+        let (Some(i), Some(edit)) = (self.selected_world, self.world_edit.take()) else {
+            return;
+        };
+
+        let dir = &self.worlds[i].0;
+        let result = match edit {
+            WorldEdit::Rename(name) if name.trim().is_empty() => Err("The name can't be empty".to_string()),
+            WorldEdit::Rename(name) => save::rename_world(dir, name.trim()),
+            WorldEdit::Delete => save::delete_world(dir)
+        };
+
+        match result {
+            Ok(()) => {
+                self.error = None;
+                self.refresh_worlds();
+            }
+
+            Err(error) => self.error = Some(error)
+        }
+    }
+
+    fn show_create_world(&mut self, ui: &mut egui::Ui) -> Option<MenuAction> {
+        let mut action = None;
+
+        ui.label(RichText::new("Create New World").size(24.0).color(Color32::WHITE));
+        ui.add_space(10.0);
+
+        egui::Grid::new("CreateWorld").num_columns(2).spacing([10.0, 8.0]).show(ui, |ui| {
+            ui.label("Name");
+            ui.text_edit_singleline(&mut self.name_input);
+            ui.end_row();
+
+            ui.label("Seed");
+            ui.add(egui::TextEdit::singleline(&mut self.seed_input).hint_text("Random"));
+            ui.end_row();
+
+            ui.label("World type");
+            egui::ComboBox::from_id_salt("WorldType").selected_text(self.generator_preset.get_name()).show_ui(ui, |ui| {
+                for preset in GeneratorPreset::ALL {
+                    ui.selectable_value(&mut self.generator_preset, preset, preset.get_name());
+                }
+            });
+            ui.end_row();
+
+            if self.generator_preset == GeneratorPreset::Superflat {
+                ui.label("Layers");
+                ui.text_edit_singleline(&mut self.generator_settings.superflat_layers);
+                ui.end_row();
+            }
+
+            if self.generator_preset == GeneratorPreset::Heightmap {
+                ui.label("PNG file");
+                ui.text_edit_singleline(&mut self.generator_settings.heightmap_path);
+                ui.end_row();
+
+                ui.label("");
+                ui.checkbox(&mut self.generator_settings.heightmap_materials, "Pick blocks by height and slope");
+                ui.end_row();
+            }
+        });
+
+        ui.add_space(20.0);
+
+        if ui.add_sized(BUTTON_SIZE, egui::Button::new(RichText::new("Create").size(18.0))).clicked() {
+            if self.name_input.trim().is_empty() {
+                self.error = Some("The name can't be empty".to_string());
+            } else {
+                action = Some(MenuAction::Create {
+                    name: self.name_input.trim().to_string(),
+                    seed: save::parse_seed(&self.seed_input).unwrap_or_else(rand::random),
+                    preset: self.generator_preset,
+                    settings: self.generator_settings.clone()
+                });
+            }
+        }
+
+        if ui.add_sized(BUTTON_SIZE, egui::Button::new(RichText::new("Cancel").size(18.0))).clicked() {
+            self.open(MenuScreen::WorldList);
+        }
+
+        action
+    }
+}
+
+/// Settings screen shared by the title and pause menu, changes are applied by `GameState` while it is open
+pub struct SettingsMenu {
+    controls: bool, // Shows the key bindings instead of the other settings
+    rebinding: Option<Action> // Action waiting for the next key or mouse button
+}
+
+impl SettingsMenu {
+    pub fn new() -> Self {
+        SettingsMenu {
+            controls: false,
+            rebinding: None
+        }
+    }
+
+    /// Binds the action waiting for input, Escape cancels instead. Returns false if no action is waiting.
+    pub fn bind(&mut self, settings: &mut Settings, binding: Binding) -> bool {
+        let Some(action) = self.rebinding else {
+            return false;
+        };
+
+        if binding == Binding::Key(KeyCode::Escape) {
+            self.rebinding = None;
+        } else if binding.is_bindable() {
+            settings.key_bindings.set(action, binding);
+            self.rebinding = None;
+        }
+
+        true
+    }
+
+    /// Returns true once "Done" is clicked, the caller saves the settings
+    pub fn show(&mut self, ui: &mut egui::Ui, settings: &mut Settings) -> bool {
+        if self.controls {
+            self.show_controls(ui, settings);
+            return false;
+        }
+
+        ui.label(RichText::new("Settings").size(32.0).color(Color32::WHITE));
+        ui.add_space(20.0);
+
+        egui::Grid::new("Settings").num_columns(2).spacing([10.0, 8.0]).show(ui, |ui| {
+            ui.label("FOV");
+            ui.add(egui::Slider::new(&mut settings.fov, settings::FOV_RANGE.0..=settings::FOV_RANGE.1).step_by(1.0));
+            ui.end_row();
+
+            ui.label("Mouse sensitivity");
+            ui.add(egui::Slider::new(&mut settings.mouse_sensitivity, settings::MOUSE_SENSITIVITY_RANGE.0..=settings::MOUSE_SENSITIVITY_RANGE.1).step_by(0.05));
+            ui.end_row();
+
+            ui.label("Render distance");
+            ui.add(egui::Slider::new(&mut settings.render_distance, settings::RENDER_DISTANCE_RANGE.0..=settings::RENDER_DISTANCE_RANGE.1).suffix(" chunks"));
+            ui.end_row();
+
+            ui.label("GUI scale");
+            ui.add(egui::Slider::new(&mut settings.gui_scale, settings::GUI_SCALE_RANGE.0..=settings::GUI_SCALE_RANGE.1).step_by(0.25));
+            ui.end_row();
+
+            ui.label("Fullscreen");
+            ui.checkbox(&mut settings.fullscreen, "");
+            ui.end_row();
+
+            ui.label("VSync");
+            ui.checkbox(&mut settings.vsync, "");
+            ui.end_row();
+
+            if cfg!(feature = "gamepad") {
+                ui.label("Gamepad dead zone");
+                ui.add(egui::Slider::new(&mut settings.gamepad_dead_zone, settings::GAMEPAD_DEAD_ZONE_RANGE.0..=settings::GAMEPAD_DEAD_ZONE_RANGE.1).step_by(0.01));
+                ui.end_row();
+
+                ui.label("Gamepad sensitivity");
+                ui.add(egui::Slider::new(&mut settings.gamepad_sensitivity, settings::MOUSE_SENSITIVITY_RANGE.0..=settings::MOUSE_SENSITIVITY_RANGE.1).step_by(0.05));
+                ui.end_row();
+            }
+        });
+
+        ui.add_space(20.0);
+
+        if ui.add_sized(BUTTON_SIZE, egui::Button::new(RichText::new("Controls").size(18.0))).clicked() {
+            self.controls = true;
+        }
+
+        ui.add_sized(BUTTON_SIZE, egui::Button::new(RichText::new("Done").size(18.0))).clicked()
+    }
+
+    fn show_controls(&mut self, ui: &mut egui::Ui, settings: &mut Settings) {
+        ui.label(RichText::new("Controls").size(32.0).color(Color32::WHITE));
+        ui.add_space(20.0);
+
+        // The hotbar slots make the list taller than small windows
+        egui::ScrollArea::vertical().max_height(LIST_HEIGHT).show(ui, |ui| {
+            egui::Grid::new("Controls").num_columns(2).spacing([10.0, 8.0]).show(ui, |ui| {
+                for action in Action::ALL {
+                    let text = if self.rebinding == Some(action) {
+                        RichText::new("> Press a key <")
+                    } else if settings.key_bindings.has_conflict(action) {
+                        RichText::new(settings.key_bindings.get(action).get_name()).color(Color32::from_rgb(255, 80, 80))
+                    } else {
+                        RichText::new(settings.key_bindings.get(action).get_name())
+                    };
+
+                    ui.label(action.get_name());
+
+                    if ui.add_sized(SMALL_BUTTON_SIZE, egui::Button::new(text)).clicked() {
+                        self.rebinding = Some(action);
+                    }
+
+                    ui.end_row();
+                }
+            });
+        });
+
+        ui.add_space(20.0);
+
+        if ui.add_sized(BUTTON_SIZE, egui::Button::new(RichText::new("Reset controls").size(18.0))).clicked() {
+            settings.key_bindings = KeyBindings::default();
+            self.rebinding = None;
+        }
+
+        if ui.add_sized(BUTTON_SIZE, egui::Button::new(RichText::new("Back").size(18.0))).clicked() {
+            self.controls = false;
+            self.rebinding = None;
+        }
+    }
+}
@@ -0,0 +1,124 @@
+// SPDX-FileCopyrightText: © 2025 - 2026 Elias Steininger <elias.st4600@gmail.com> and Project Contributors (see CONTRIBUTORS.md)
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use crate::input::KeyBindings;
+use crate::world::save::parse_key_values;
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+pub const FOV_RANGE: (f32, f32) = (30.0, 110.0);
+pub const MOUSE_SENSITIVITY_RANGE: (f32, f32) = (0.1, 3.0);
+pub const RENDER_DISTANCE_RANGE: (i32, i32) = (1, 8); // In chunks
+pub const GUI_SCALE_RANGE: (f32, f32) = (0.5, 2.0);
+pub const GAMEPAD_DEAD_ZONE_RANGE: (f32, f32) = (0.0, 0.5);
+const MIN_WINDOW_SIZE: u32 = 200;
+
+/// Options the player can change, saved in the user's config directory
+#[derive(Clone, PartialEq)]
+pub struct Settings {
+    pub fov: f32,
+    pub mouse_sensitivity: f32, // Multiplier of the player's base mouse sensitivity
+    pub render_distance: i32,
+    pub gui_scale: f32,
+    pub fullscreen: bool,
+    pub vsync: bool,
+    pub window_width: u32, // Window size to open with, only updated outside of fullscreen
+    pub window_height: u32,
+    pub key_bindings: KeyBindings,
+    pub gamepad_dead_zone: f32, // Share of the stick range around the center that is ignored
+    pub gamepad_sensitivity: f32 // Multiplier of how fast the right stick turns the camera
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            fov: 90.0,
+            mouse_sensitivity: 1.0,
+            render_distance: 2,
+            gui_scale: 1.0,
+            fullscreen: false,
+            vsync: true,
+            window_width: 1200,
+            window_height: 800,
+            key_bindings: KeyBindings::default(),
+            gamepad_dead_zone: 0.15,
+            gamepad_sensitivity: 1.0
+        }
+    }
+}
+
+impl Settings {
+    /// Loads the config file, missing or invalid values keep their defaults
+    pub fn load() -> Self {
+        fs::read_to_string(get_config_path()).map(|text| Self::from_text(&text)).unwrap_or_default()
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let path = get_config_path();
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|error| format!("Failed to create '{}': {error}", dir.display()))?;
+        }
+
+        fs::write(&path, self.to_text()).map_err(|error| format!("Failed to write '{}': {error}", path.display()))
+    }
+
+    fn to_text(&self) -> String {
+        format!(
+            "fov = {}\nmouse_sensitivity = {}\nrender_distance = {}\ngui_scale = {}\nfullscreen = {}\nvsync = {}\nwindow_width = {}\nwindow_height = {}\ngamepad_dead_zone = {}\ngamepad_sensitivity = {}\n",
+            self.fov, self.mouse_sensitivity, self.render_distance, self.gui_scale, self.fullscreen, self.vsync, self.window_width, self.window_height, self.gamepad_dead_zone, self.gamepad_sensitivity
+        ) + &self.key_bindings.to_text()
+    }
+
+    fn from_text(text: &str) -> Self {
+        let values = parse_key_values(text);
+        let defaults = Settings::default();
+
+        Settings {
+            fov: get_value(&values, "fov", defaults.fov).clamp(FOV_RANGE.0, FOV_RANGE.1),
+            mouse_sensitivity: get_value(&values, "mouse_sensitivity", defaults.mouse_sensitivity).clamp(MOUSE_SENSITIVITY_RANGE.0, MOUSE_SENSITIVITY_RANGE.1),
+            render_distance: get_value(&values, "render_distance", defaults.render_distance).clamp(RENDER_DISTANCE_RANGE.0, RENDER_DISTANCE_RANGE.1),
+            gui_scale: get_value(&values, "gui_scale", defaults.gui_scale).clamp(GUI_SCALE_RANGE.0, GUI_SCALE_RANGE.1),
+            fullscreen: get_value(&values, "fullscreen", defaults.fullscreen),
+            vsync: get_value(&values, "vsync", defaults.vsync),
+            window_width: get_value(&values, "window_width", defaults.window_width).max(MIN_WINDOW_SIZE),
+            window_height: get_value(&values, "window_height", defaults.window_height).max(MIN_WINDOW_SIZE),
+            key_bindings: KeyBindings::from_values(&values),
+            gamepad_dead_zone: get_value(&values, "gamepad_dead_zone", defaults.gamepad_dead_zone).clamp(GAMEPAD_DEAD_ZONE_RANGE.0, GAMEPAD_DEAD_ZONE_RANGE.1),
+            gamepad_sensitivity: get_value(&values, "gamepad_sensitivity", defaults.gamepad_sensitivity).clamp(MOUSE_SENSITIVITY_RANGE.0, MOUSE_SENSITIVITY_RANGE.1)
+        }
+    }
+}
+
+fn get_value<T: FromStr>(values: &HashMap<String, String>, key: &str, default: T) -> T {
+    values.get(key).and_then(|value| value.parse().ok()).unwrap_or(default)
+}
+
+fn get_config_path() -> PathBuf {
+    dirs_next::config_dir().unwrap_or_default().join("Brickbyte").join("settings.txt")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn settings_round_trip_through_text() {
+        let settings = Settings {fov: 70.0, mouse_sensitivity: 1.5, render_distance: 4, gui_scale: 1.25, fullscreen: true, vsync: false, window_width: 1920, window_height: 1080, key_bindings: KeyBindings::default(), gamepad_dead_zone: 0.2, gamepad_sensitivity: 2.0};
+
+        assert!(Settings::from_text(&settings.to_text()) == settings);
+    }
+
+    #[test]
+    fn invalid_values_fall_back_to_defaults() {
+        let settings = Settings::from_text("fov = wide\nrender_distance = 100\n# gui_scale = 2\nvsync = maybe\nwindow_width = 5");
+
+        assert_eq!(settings.fov, Settings::default().fov);
+        assert_eq!(settings.render_distance, RENDER_DISTANCE_RANGE.1);
+        assert_eq!(settings.gui_scale, Settings::default().gui_scale);
+        assert_eq!(settings.vsync, Settings::default().vsync);
+        assert_eq!(settings.window_width, MIN_WINDOW_SIZE);
+    }
+}
@@ -0,0 +1,60 @@
+// SPDX-FileCopyrightText: © 2025 - 2026 Elias Steininger <elias.st4600@gmail.com> and Project Contributors (see CONTRIBUTORS.md)
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use crate::world::block;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Biome {
+    Ocean,
+    Plains,
+    Desert,
+    Forest,
+    Mountains,
+    Snow
+}
+
+pub struct BiomeProperties {
+    pub surface: u8, // Top block of every column
+    pub filler: u8, // Blocks between the surface and the stone
+    pub base_height: f32,
+    pub height_scale: f32, // How far the terrain deviates from the base height
+    pub tree_chance: f32, // Chance per column to grow a tree
+    pub plant_chance: f32 // Chance per grass column to grow tall grass or a flower
+}
+
+const OCEAN: BiomeProperties = BiomeProperties {surface: block::SAND, filler: block::SAND, base_height: 96.0, height_scale: 6.0, tree_chance: 0.0, plant_chance: 0.0};
+const PLAINS: BiomeProperties = BiomeProperties {surface: block::GRASS, filler: block::DIRT, base_height: 118.0, height_scale: 4.0, tree_chance: 0.002, plant_chance: 0.2};
+const DESERT: BiomeProperties = BiomeProperties {surface: block::SAND, filler: block::SAND, base_height: 117.0, height_scale: 5.0, tree_chance: 0.0, plant_chance: 0.0};
+const FOREST: BiomeProperties = BiomeProperties {surface: block::GRASS, filler: block::DIRT, base_height: 120.0, height_scale: 7.0, tree_chance: 0.03, plant_chance: 0.1};
+const MOUNTAINS: BiomeProperties = BiomeProperties {surface: block::GRASS, filler: block::DIRT, base_height: 135.0, height_scale: 30.0, tree_chance: 0.005, plant_chance: 0.05};
+const SNOW: BiomeProperties = BiomeProperties {surface: block::SNOW, filler: block::DIRT, base_height: 122.0, height_scale: 10.0, tree_chance: 0.004, plant_chance: 0.0};
+
+impl Biome {
+    /// Picks the biome for a temperature and humidity, both roughly in -1.0..1.0
+    pub fn from_climate(temperature: f64, humidity: f64) -> Biome {
+        if humidity > 0.45 {
+            Biome::Ocean
+        } else if temperature < -0.35 {
+            Biome::Snow
+        } else if temperature > 0.3 && humidity < -0.1 {
+            Biome::Desert
+        } else if humidity > 0.15 {
+            Biome::Forest
+        } else if temperature < 0.0 {
+            Biome::Mountains
+        } else {
+            Biome::Plains
+        }
+    }
+
+    pub fn get_properties(&self) -> &'static BiomeProperties {
+        match self {
+            Biome::Ocean => &OCEAN,
+            Biome::Plains => &PLAINS,
+            Biome::Desert => &DESERT,
+            Biome::Forest => &FOREST,
+            Biome::Mountains => &MOUNTAINS,
+            Biome::Snow => &SNOW
+        }
+    }
+}
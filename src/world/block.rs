@@ -0,0 +1,115 @@
+// SPDX-FileCopyrightText: © 2025 - 2026 Elias Steininger <elias.st4600@gmail.com> and Project Contributors (see CONTRIBUTORS.md)
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use crate::world::item::ToolTier;
+
+pub const AIR: u8 = 0;
+pub const GRASS: u8 = 1;
+pub const DIRT: u8 = 2;
+pub const STONE: u8 = 3;
+pub const BRICKS: u8 = 4;
+pub const COBBLESTONE: u8 = 5;
+pub const COAL_ORE: u8 = 6;
+pub const IRON_ORE: u8 = 7;
+pub const GOLD_ORE: u8 = 8;
+pub const DIAMOND_ORE: u8 = 9;
+pub const WATER: u8 = 10;
+pub const LAVA: u8 = 11;
+pub const OBSIDIAN: u8 = 12;
+pub const LOG: u8 = 13;
+pub const LEAVES: u8 = 14;
+pub const SAPLING: u8 = 15;
+pub const SAND: u8 = 16;
+pub const GRAVEL: u8 = 17;
+pub const SNOW: u8 = 18;
+pub const BEDROCK: u8 = 19;
+pub const TALL_GRASS: u8 = 20;
+pub const YELLOW_FLOWER: u8 = 21;
+pub const RED_FLOWER: u8 = 22;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BlockShape {
+    Cube,
+    Cross // Two crossed quads, used for plants
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BlockCategory {
+    None,
+    Soil,
+    Stone,
+    Wood
+}
+
+pub struct BlockProperties {
+    pub name: &'static str,
+    pub hardness: f32, // Seconds to break with the right tool at speed 1, negative means unbreakable
+    pub category: BlockCategory,
+    pub required_tier: Option<ToolTier>, // Minimum tool tier needed to get a drop
+    pub drop: u8, // Air drops nothing
+    pub solid: bool, // Blocks entity movement
+    pub opaque: bool, // Hides neighbouring faces and blocks light
+    pub shape: BlockShape
+}
+
+pub const BLOCK_COUNT: usize = 23;
+
+const BLOCKS: [BlockProperties; BLOCK_COUNT] = [
+    BlockProperties {name: "air", hardness: 0.0, category: BlockCategory::None, required_tier: None, drop: AIR, solid: false, opaque: false, shape: BlockShape::Cube},
+    BlockProperties {name: "grass", hardness: 0.6, category: BlockCategory::Soil, required_tier: None, drop: DIRT, solid: true, opaque: true, shape: BlockShape::Cube},
+    BlockProperties {name: "dirt", hardness: 0.5, category: BlockCategory::Soil, required_tier: None, drop: DIRT, solid: true, opaque: true, shape: BlockShape::Cube},
+    BlockProperties {name: "stone", hardness: 1.5, category: BlockCategory::Stone, required_tier: Some(ToolTier::Wood), drop: COBBLESTONE, solid: true, opaque: true, shape: BlockShape::Cube},
+    BlockProperties {name: "bricks", hardness: 2.0, category: BlockCategory::Stone, required_tier: Some(ToolTier::Wood), drop: BRICKS, solid: true, opaque: true, shape: BlockShape::Cube},
+    BlockProperties {name: "cobblestone", hardness: 2.0, category: BlockCategory::Stone, required_tier: Some(ToolTier::Wood), drop: COBBLESTONE, solid: true, opaque: true, shape: BlockShape::Cube},
+    BlockProperties {name: "coal_ore", hardness: 3.0, category: BlockCategory::Stone, required_tier: Some(ToolTier::Wood), drop: COAL_ORE, solid: true, opaque: true, shape: BlockShape::Cube},
+    BlockProperties {name: "iron_ore", hardness: 3.0, category: BlockCategory::Stone, required_tier: Some(ToolTier::Stone), drop: IRON_ORE, solid: true, opaque: true, shape: BlockShape::Cube},
+    BlockProperties {name: "gold_ore", hardness: 3.0, category: BlockCategory::Stone, required_tier: Some(ToolTier::Iron), drop: GOLD_ORE, solid: true, opaque: true, shape: BlockShape::Cube},
+    BlockProperties {name: "diamond_ore", hardness: 3.0, category: BlockCategory::Stone, required_tier: Some(ToolTier::Iron), drop: DIAMOND_ORE, solid: true, opaque: true, shape: BlockShape::Cube},
+    BlockProperties {name: "water", hardness: -1.0, category: BlockCategory::None, required_tier: None, drop: AIR, solid: false, opaque: false, shape: BlockShape::Cube},
+    BlockProperties {name: "lava", hardness: -1.0, category: BlockCategory::None, required_tier: None, drop: AIR, solid: false, opaque: false, shape: BlockShape::Cube},
+    BlockProperties {name: "obsidian", hardness: 50.0, category: BlockCategory::Stone, required_tier: Some(ToolTier::Diamond), drop: OBSIDIAN, solid: true, opaque: true, shape: BlockShape::Cube},
+    BlockProperties {name: "log", hardness: 2.0, category: BlockCategory::Wood, required_tier: None, drop: LOG, solid: true, opaque: true, shape: BlockShape::Cube},
+    BlockProperties {name: "leaves", hardness: 0.2, category: BlockCategory::None, required_tier: None, drop: SAPLING, solid: true, opaque: false, shape: BlockShape::Cube},
+    BlockProperties {name: "sapling", hardness: 0.0, category: BlockCategory::None, required_tier: None, drop: SAPLING, solid: false, opaque: false, shape: BlockShape::Cross},
+    BlockProperties {name: "sand", hardness: 0.5, category: BlockCategory::Soil, required_tier: None, drop: SAND, solid: true, opaque: true, shape: BlockShape::Cube},
+    BlockProperties {name: "gravel", hardness: 0.6, category: BlockCategory::Soil, required_tier: None, drop: GRAVEL, solid: true, opaque: true, shape: BlockShape::Cube},
+    BlockProperties {name: "snow", hardness: 0.2, category: BlockCategory::Soil, required_tier: None, drop: SNOW, solid: true, opaque: true, shape: BlockShape::Cube},
+    BlockProperties {name: "bedrock", hardness: -1.0, category: BlockCategory::None, required_tier: None, drop: AIR, solid: true, opaque: true, shape: BlockShape::Cube},
+    BlockProperties {name: "tall_grass", hardness: 0.0, category: BlockCategory::None, required_tier: None, drop: AIR, solid: false, opaque: false, shape: BlockShape::Cross},
+    BlockProperties {name: "yellow_flower", hardness: 0.0, category: BlockCategory::None, required_tier: None, drop: YELLOW_FLOWER, solid: false, opaque: false, shape: BlockShape::Cross},
+    BlockProperties {name: "red_flower", hardness: 0.0, category: BlockCategory::None, required_tier: None, drop: RED_FLOWER, solid: false, opaque: false, shape: BlockShape::Cross}
+];
+
+pub fn get_properties(id: u8) -> &'static BlockProperties {
+    BLOCKS.get(id as usize).unwrap_or(&BLOCKS[AIR as usize])
+}
+
+pub fn get_id(name: &str) -> Option<u8> {
+    BLOCKS.iter().position(|properties| properties.name == name).map(|id| id as u8)
+}
+
+pub fn is_solid(id: u8) -> bool {
+    get_properties(id).solid
+}
+
+pub fn is_opaque(id: u8) -> bool {
+    get_properties(id).opaque
+}
+
+pub fn is_fluid(id: u8) -> bool {
+    id == WATER || id == LAVA
+}
+
+/// Blocks that fall down when there is nothing below them
+pub fn has_gravity(id: u8) -> bool {
+    id == SAND || id == GRAVEL
+}
+
+/// Plants that pop off when the soil below them is removed
+pub fn needs_soil(id: u8) -> bool {
+    get_properties(id).shape == BlockShape::Cross
+}
+
+pub fn is_soil(id: u8) -> bool {
+    id == GRASS || id == DIRT
+}
@@ -6,9 +6,11 @@ use glow::{Context, HasContext, NativeBuffer, NativeTexture, NativeVertexArray,
 
 pub const CHUNK_DIMENSION: u8 = 16;
 pub const CHUNK_HEIGHT: u8 = 208;
+const FLOATS_PER_VERTEX: usize = 6; // Position, texture coordinates and light
 
 pub struct Chunk {
     blocks: Vec<u8>,
+    fluid_levels: Vec<u8>,
     pub light_map: Vec<u8>,
     pub position: IVec2,
     shader: Program,
@@ -23,6 +25,7 @@ impl Chunk {
     pub fn new(position: IVec2, shader: Program) -> Self {
         Chunk {
             blocks: vec![0; (CHUNK_DIMENSION as usize) * (CHUNK_HEIGHT as usize) * (CHUNK_DIMENSION as usize)],
+            fluid_levels: vec![0; (CHUNK_DIMENSION as usize) * (CHUNK_HEIGHT as usize) * (CHUNK_DIMENSION as usize)],
             light_map: vec![0; (CHUNK_DIMENSION as usize) * (CHUNK_HEIGHT as usize) * (CHUNK_DIMENSION as usize)],
             position,
             shader,
@@ -47,6 +50,11 @@ impl Chunk {
         }
     }
     
+    /// Vertices in the chunk's mesh, 0 before it is built
+    pub fn get_vertex_count(&self) -> usize {
+        self.vertices.as_ref().map_or(0, |vertices| vertices.len() / FLOATS_PER_VERTEX)
+    }
+
     pub fn setup_buffers(&mut self, gl: &Context) {
         unsafe {
             let vao = gl.create_vertex_array().unwrap();
@@ -56,7 +64,7 @@ impl Chunk {
             gl.bind_buffer(glow::ARRAY_BUFFER, Some(vbo));
             gl.buffer_data_u8_slice(glow::ARRAY_BUFFER, self.vertices.as_ref().unwrap().align_to::<u8>().1, glow::STATIC_DRAW);
 
-            const STRIDE: i32 = 6 * size_of::<f32>() as i32;
+            const STRIDE: i32 = (FLOATS_PER_VERTEX * size_of::<f32>()) as i32;
             
             gl.vertex_attrib_pointer_f32(0, 3, glow::FLOAT, false, STRIDE, 0);
             gl.enable_vertex_attrib_array(0);
@@ -81,11 +89,31 @@ impl Chunk {
         }
     }
     
+    pub fn delete_buffers(&mut self, gl: &Context) {
+        unsafe {
+            if let Some(vao) = self.vertex_array_object.take() {
+                gl.delete_vertex_array(vao);
+            }
+            if let Some(vbo) = self.vertex_buffer_object.take() {
+                gl.delete_buffer(vbo);
+            }
+            if let Some(ebo) = self.element_buffer_object.take() {
+                gl.delete_buffer(ebo);
+            }
+        }
+    }
+
     pub fn render(&self, gl: &Context, pv: Mat4, texture: Option<NativeTexture>) {
+        let model = Mat4::from_translation(Vec3::new((self.position.x as f32) * CHUNK_DIMENSION as f32, 0.0, (self.position.y as f32) * CHUNK_DIMENSION as f32));
+
+        self.render_with_model(gl, pv, model, texture);
+    }
+
+    /// Draws the mesh with its own model matrix instead of the chunk's position, for meshes that aren't part of the terrain
+    pub fn render_with_model(&self, gl: &Context, pv: Mat4, model: Mat4, texture: Option<NativeTexture>) {
         unsafe {
             gl.bind_texture(glow::TEXTURE_2D, texture);
-            
-            let model = Mat4::from_translation(Vec3::new((self.position.x as f32) * CHUNK_DIMENSION as f32, 0.0, (self.position.y as f32) * CHUNK_DIMENSION as f32));
+
             let mvp = pv * model;
             
             gl.use_program(Some(self.shader));
@@ -112,6 +140,40 @@ impl Chunk {
     pub fn set_block(&mut self, pos: IVec3, id: u8) {
         if pos.x >= 0 && pos.x < CHUNK_DIMENSION as i32 && pos.y >= 0 && pos.y < CHUNK_HEIGHT as i32 && pos.z >= 0 && pos.z < CHUNK_DIMENSION as i32 {
             self.blocks[Self::get_block_index(pos)] = id;
+            self.fluid_levels[Self::get_block_index(pos)] = 0;
+        }
+    }
+
+    /// Flow level of the fluid in this cell, 0 is a source block (see `fluid.rs`)
+    pub fn get_fluid_level(&self, pos: IVec3) -> u8 {
+        if pos.x < 0 || pos.x >= CHUNK_DIMENSION as i32 || pos.y < 0 || pos.y >= CHUNK_HEIGHT as i32 || pos.z < 0 || pos.z >= CHUNK_DIMENSION as i32 {
+            return 0;
+        }
+
+        self.fluid_levels[Self::get_block_index(pos)]
+    }
+
+    pub fn set_fluid_level(&mut self, pos: IVec3, level: u8) {
+        if pos.x >= 0 && pos.x < CHUNK_DIMENSION as i32 && pos.y >= 0 && pos.y < CHUNK_HEIGHT as i32 && pos.z >= 0 && pos.z < CHUNK_DIMENSION as i32 {
+            self.fluid_levels[Self::get_block_index(pos)] = level;
         }
     }
+
+    /// Blocks followed by fluid levels, the layout used for save files
+    pub fn get_data(&self) -> Vec<u8> {
+        [self.blocks.as_slice(), self.fluid_levels.as_slice()].concat()
+    }
+
+    /// Restores blocks and fluid levels from `get_data`, returns false if the data has the wrong size
+    pub fn set_data(&mut self, data: &[u8]) -> bool {
+        if data.len() != self.blocks.len() * 2 {
+            return false;
+        }
+
+        let (blocks, fluid_levels) = data.split_at(self.blocks.len());
+        self.blocks.copy_from_slice(blocks);
+        self.fluid_levels.copy_from_slice(fluid_levels);
+
+        true
+    }
 }
@@ -0,0 +1,146 @@
+// SPDX-FileCopyrightText: © 2025 - 2026 Elias Steininger <elias.st4600@gmail.com> and Project Contributors (see CONTRIBUTORS.md)
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use crate::world::block;
+use crate::world::world::World;
+use glam::{BVec3, IVec3, Vec3};
+
+// Faces that exactly touch a block are not counted as overlapping
+const EPSILON: f32 = 0.001;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Aabb {
+    pub min: Vec3,
+    pub max: Vec3
+}
+
+impl Aabb {
+    /// Box with its bottom face centered on `pos`
+    pub fn from_feet(pos: Vec3, width: f32, height: f32) -> Self {
+        Aabb {
+            min: Vec3::new(pos.x - width / 2.0, pos.y, pos.z - width / 2.0),
+            max: Vec3::new(pos.x + width / 2.0, pos.y + height, pos.z + width / 2.0)
+        }
+    }
+
+    pub fn offset(&self, offset: Vec3) -> Self {
+        Aabb {
+            min: self.min + offset,
+            max: self.max + offset
+        }
+    }
+
+    pub fn intersects_block(&self, block_pos: IVec3) -> bool {
+        let block_min = block_pos.as_vec3();
+        let block_max = block_min + Vec3::ONE;
+
+        self.min.x < block_max.x - EPSILON && self.max.x > block_min.x + EPSILON &&
+        self.min.y < block_max.y - EPSILON && self.max.y > block_min.y + EPSILON &&
+        self.min.z < block_max.z - EPSILON && self.max.z > block_min.z + EPSILON
+    }
+
+    /// Range of block coordinates the box overlaps on the given axis
+    fn block_range(&self, axis: usize) -> (i32, i32) {
+        ((self.min[axis] + EPSILON).floor() as i32, (self.max[axis] - EPSILON).floor() as i32)
+    }
+}
+
+/// Moves the box through the world one axis at a time (Y, X, Z), stopping in front of the first solid block on each axis.
+/// Every block layer between the start and the target is checked, so fast movement can't tunnel through thin walls.
+/// Returns the distance actually moved and which axes were blocked.
+pub fn move_and_collide(world: &World, aabb: Aabb, motion: Vec3) -> (Vec3, BVec3) {
+    let mut current = aabb;
+    let mut moved = Vec3::ZERO;
+    let mut collided = BVec3::FALSE;
+
+    for axis in [1, 0, 2] {
+        let (distance, hit) = sweep_axis(world, &current, axis, motion[axis]);
+
+        let mut offset = Vec3::ZERO;
+        offset[axis] = distance;
+
+        current = current.offset(offset);
+        moved[axis] = distance;
+        collided.set(axis, hit);
+    }
+
+    (moved, collided)
+}
+
+/// Whether any solid block overlaps the box
+pub fn collides(world: &World, aabb: &Aabb) -> bool {
+    any_block(world, aabb, block::is_solid)
+}
+
+/// Whether any block overlapping the box matches the predicate
+pub fn any_block(world: &World, aabb: &Aabb, predicate: impl Fn(u8) -> bool) -> bool {
+    let (min_x, max_x) = aabb.block_range(0);
+    let (min_y, max_y) = aabb.block_range(1);
+    let (min_z, max_z) = aabb.block_range(2);
+
+    for x in min_x..=max_x {
+        for y in min_y..=max_y {
+            for z in min_z..=max_z {
+                if predicate(world.get_global_block(IVec3::new(x, y, z))) {
+                    return true;
+                }
+            }
+        }
+    }
+
+    false
+}
+
+fn sweep_axis(world: &World, aabb: &Aabb, axis: usize, distance: f32) -> (f32, bool) {
+    if distance == 0.0 {
+        return (0.0, false);
+    }
+
+    let (axis_1, axis_2) = match axis {
+        0 => (1, 2),
+        1 => (0, 2),
+        _ => (0, 1)
+    };
+
+    let (min_1, max_1) = aabb.block_range(axis_1);
+    let (min_2, max_2) = aabb.block_range(axis_2);
+
+    let layer_is_solid = |layer: i32| {
+        for a in min_1..=max_1 {
+            for b in min_2..=max_2 {
+                let mut block_pos = IVec3::ZERO;
+                block_pos[axis] = layer;
+                block_pos[axis_1] = a;
+                block_pos[axis_2] = b;
+
+                if block::is_solid(world.get_global_block(block_pos)) {
+                    return true;
+                }
+            }
+        }
+
+        false
+    };
+
+    if distance > 0.0 {
+        let first_layer = (aabb.max[axis] - EPSILON).floor() as i32 + 1;
+        let last_layer = (aabb.max[axis] + distance).ceil() as i32 - 1;
+
+        for layer in first_layer..=last_layer {
+            if layer_is_solid(layer) {
+                return ((layer as f32 - aabb.max[axis]).max(0.0), true);
+            }
+        }
+    } else {
+        let first_layer = (aabb.min[axis] + EPSILON).floor() as i32 - 1;
+        let last_layer = (aabb.min[axis] + distance).floor() as i32;
+
+        for layer in (last_layer..=first_layer).rev() {
+            if layer_is_solid(layer) {
+                return (((layer + 1) as f32 - aabb.min[axis]).min(0.0), true);
+            }
+        }
+    }
+
+    (distance, false)
+}
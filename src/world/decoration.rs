@@ -0,0 +1,128 @@
+// SPDX-FileCopyrightText: © 2025 - 2026 Elias Steininger <elias.st4600@gmail.com> and Project Contributors (see CONTRIBUTORS.md)
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use crate::world::biome::Biome;
+use crate::world::block;
+use crate::world::chunk::{Chunk, CHUNK_HEIGHT};
+use crate::world::random_tick;
+use glam::IVec3;
+use rand::{Rng, RngExt};
+
+const FLOWER_CHANCE: f64 = 0.125; // Share of plants that are flowers instead of tall grass
+
+/// Adds the decoration for one surface column to `writes`, in world coordinates.
+/// Decoration may reach into neighbouring chunks, so it is only applied through `apply_write`.
+pub fn decorate_column(surface_pos: IVec3, surface: u8, biome: Biome, rng: &mut impl Rng, writes: &mut Vec<(IVec3, u8)>) {
+    let properties = biome.get_properties();
+    let base = surface_pos + IVec3::Y;
+
+    if (surface == block::GRASS || surface == block::SNOW) && rng.random_bool(properties.tree_chance as f64) {
+        let trunk_height = rng.random_range(random_tick::MIN_TRUNK_HEIGHT..=random_tick::MAX_TRUNK_HEIGHT);
+
+        for (offset, id) in random_tick::get_tree_blocks(trunk_height, rng) {
+            writes.push((base + offset, id));
+        }
+    } else if surface == block::GRASS && rng.random_bool(properties.plant_chance as f64) {
+        let id = if !rng.random_bool(FLOWER_CHANCE) {
+            block::TALL_GRASS
+        } else if rng.random_bool(0.5) {
+            block::YELLOW_FLOWER
+        } else {
+            block::RED_FLOWER
+        };
+
+        writes.push((base, id));
+    }
+}
+
+/// Decoration only replaces air or decoration of a lower priority, so the result is the same in whichever order the writes arrive
+fn get_priority(id: u8) -> Option<u8> {
+    match id {
+        block::AIR => Some(0),
+        block::TALL_GRASS | block::YELLOW_FLOWER | block::RED_FLOWER => Some(1),
+        block::LEAVES => Some(2),
+        block::LOG => Some(3),
+        _ => None
+    }
+}
+
+/// Writes a decoration block at a local position of the chunk if it may replace the block there
+pub fn apply_write(chunk: &mut Chunk, pos: IVec3, id: u8) {
+    if !(0..CHUNK_HEIGHT as i32).contains(&pos.y) {
+        return;
+    }
+
+    if let (Some(current), Some(new)) = (get_priority(chunk.get_block(pos)), get_priority(id)) && new > current {
+        chunk.set_block(pos, id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::terrain::TerrainGenerator;
+    use crate::world::test_util::{get_dummy_shader, new_chunk};
+    use crate::world::world::World;
+    use glam::IVec2;
+
+    const SEED: u64 = 12345678910;
+
+    fn generate_world(generator: &TerrainGenerator, origin: IVec2, reverse: bool) -> World {
+        let mut world = World::new();
+        let mut positions: Vec<IVec2> = (0..16).map(|i| origin + IVec2::new(i % 4, i / 4)).collect();
+
+        if reverse {
+            positions.reverse();
+        }
+
+        for pos in positions {
+            world.insert_chunk(pos, get_dummy_shader(), generator);
+        }
+
+        world
+    }
+
+    #[test]
+    fn decoration_does_not_depend_on_generation_order() {
+        let generator = TerrainGenerator::new(SEED);
+
+        // Start in a forest, so there are plenty of trees on chunk borders
+        let origin = (-64..64).map(|x| IVec2::new(x, 0)).find(|pos| generator.get_biome(pos.x * 16, 0) == Biome::Forest).unwrap();
+        let forward = generate_world(&generator, origin, false);
+        let reverse = generate_world(&generator, origin, true);
+        let mut logs = 0;
+
+        for x in origin.x * 16..(origin.x + 4) * 16 {
+            for z in 0..64 {
+                for y in 0..CHUNK_HEIGHT as i32 {
+                    let pos = IVec3::new(x, y, z);
+
+                    assert_eq!(forward.get_global_block(pos), reverse.get_global_block(pos));
+
+                    if forward.get_global_block(pos) == block::LOG {
+                        logs += 1;
+                    }
+                }
+            }
+        }
+
+        assert!(logs > 0);
+    }
+
+    #[test]
+    fn logs_replace_leaves_but_not_terrain() {
+        let mut chunk = new_chunk(IVec2::ZERO);
+        let pos = IVec3::new(1, 10, 1);
+
+        apply_write(&mut chunk, pos, block::LEAVES);
+        apply_write(&mut chunk, pos, block::TALL_GRASS);
+        assert_eq!(chunk.get_block(pos), block::LEAVES);
+
+        apply_write(&mut chunk, pos, block::LOG);
+        assert_eq!(chunk.get_block(pos), block::LOG);
+
+        chunk.set_block(pos, block::STONE);
+        apply_write(&mut chunk, pos, block::LOG);
+        assert_eq!(chunk.get_block(pos), block::STONE);
+    }
+}
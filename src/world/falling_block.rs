@@ -0,0 +1,162 @@
+// SPDX-FileCopyrightText: © 2025 - 2026 Elias Steininger <elias.st4600@gmail.com> and Project Contributors (see CONTRIBUTORS.md)
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use crate::world::block;
+use crate::world::world::World;
+use glam::{IVec3, Mat4, Vec2, Vec3};
+
+pub const FALL_DELAY: u64 = 2; // Ticks between losing support and starting to fall
+const GRAVITY: f32 = 0.04; // Blocks per tick²
+const DRAG: f32 = 0.98;
+
+/// A block that lost its support and is falling as an entity until it lands
+pub struct FallingBlock {
+    pub id: u8,
+    pub pos: Vec3, // Minimum corner of the block
+    velocity: f32
+}
+
+impl FallingBlock {
+    pub fn new(id: u8, block_pos: IVec3) -> Self {
+        FallingBlock {
+            id,
+            pos: block_pos.as_vec3(),
+            velocity: 0.0
+        }
+    }
+
+    /// Moves the block by one tick, returns true once it has landed (or fell out of the world) and should be removed
+    pub fn update(&mut self, world: &mut World) -> bool {
+        self.velocity = (self.velocity - GRAVITY) * DRAG;
+
+        let x = self.pos.x as i32;
+        let z = self.pos.z as i32;
+        let target_y = self.pos.y + self.velocity;
+
+        // Check every cell passed this tick, so fast blocks can't fall through thin floors
+        for y in ((target_y.floor() as i32)..(self.pos.y.floor() as i32)).rev() {
+            if y < 0 {
+                return true;
+            }
+
+            if block::is_solid(world.get_global_block(IVec3::new(x, y, z))) {
+                let landing_pos = IVec3::new(x, y + 1, z);
+
+                if is_replaceable(world.get_global_block(landing_pos)) {
+                    world.update_block(landing_pos, self.id, 0);
+                }
+
+                return true;
+            }
+        }
+
+        self.pos.y = target_y;
+
+        false
+    }
+
+    /// Moves the unit cube from `get_cube_mesh` to the block's position
+    pub fn get_model(&self) -> Mat4 {
+        Mat4::from_translation(self.pos)
+    }
+}
+
+/// Vertices and indices of a textured unit cube with its minimum corner at the origin
+pub fn get_cube_mesh(texture_coords: [Vec2; 4]) -> (Vec<f32>, Vec<i32>) {
+    let mut vertices: Vec<f32> = Vec::new();
+    let mut indices: Vec<i32> = Vec::new();
+
+    // Corners of each face with its brightness, in the same winding as the chunk faces
+    let faces: [([Vec3; 4], f32); 6] = [
+        ([Vec3::new(0.0, 0.0, 1.0), Vec3::new(1.0, 0.0, 1.0), Vec3::new(1.0, 1.0, 1.0), Vec3::new(0.0, 1.0, 1.0)], 0.8),
+        ([Vec3::new(1.0, 0.0, 0.0), Vec3::new(0.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.0), Vec3::new(1.0, 1.0, 0.0)], 0.8),
+        ([Vec3::new(0.0, 1.0, 0.0), Vec3::new(1.0, 1.0, 0.0), Vec3::new(1.0, 1.0, 1.0), Vec3::new(0.0, 1.0, 1.0)], 1.0),
+        ([Vec3::new(0.0, 0.0, 1.0), Vec3::new(1.0, 0.0, 1.0), Vec3::new(1.0, 0.0, 0.0), Vec3::new(0.0, 0.0, 0.0)], 0.4),
+        ([Vec3::new(0.0, 0.0, 0.0), Vec3::new(0.0, 0.0, 1.0), Vec3::new(0.0, 1.0, 1.0), Vec3::new(0.0, 1.0, 0.0)], 0.8),
+        ([Vec3::new(1.0, 0.0, 1.0), Vec3::new(1.0, 0.0, 0.0), Vec3::new(1.0, 1.0, 0.0), Vec3::new(1.0, 1.0, 1.0)], 0.8)
+    ];
+
+    for (corners, brightness) in faces {
+        let index = (vertices.len() / 6) as i32;
+
+        for i in 0..4 {
+            vertices.extend_from_slice(&[corners[i].x, corners[i].y, corners[i].z, texture_coords[i].x, texture_coords[i].y, brightness]);
+        }
+
+        indices.extend_from_slice(&[index, index + 1, index + 2, index + 2, index + 3, index]);
+    }
+
+    (vertices, indices)
+}
+
+/// Whether a falling block may land in a cell with this block
+fn is_replaceable(id: u8) -> bool {
+    id == block::AIR || block::is_fluid(id)
+}
+
+/// Starts a gravity block falling if nothing supports it anymore
+pub fn update_gravity_block(world: &mut World, pos: IVec3) {
+    let id = world.get_global_block(pos);
+
+    if !block::has_gravity(id) || pos.y <= 0 || !is_replaceable(world.get_global_block(pos - IVec3::Y)) {
+        return;
+    }
+
+    world.update_block(pos, block::AIR, 0);
+    world.spawn_falling_block(FallingBlock::new(id, pos));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::test_util::{run_ticks, world_with_floor};
+
+    const FLOOR_Y: i32 = 10;
+
+    #[test]
+    fn sand_falls_when_support_is_removed() {
+        let mut world = world_with_floor(FLOOR_Y, block::STONE);
+        let support = IVec3::new(2, FLOOR_Y + 5, 2);
+
+        world.update_block(support, block::STONE, 0);
+        world.update_block(support + IVec3::Y, block::SAND, 0);
+        run_ticks(&mut world, 10);
+        assert_eq!(world.get_global_block(support + IVec3::Y), block::SAND);
+
+        world.update_block(support, block::AIR, 0);
+        run_ticks(&mut world, 3);
+        assert_eq!(world.get_global_block(support + IVec3::Y), block::AIR);
+
+        run_ticks(&mut world, 100);
+        assert_eq!(world.get_global_block(IVec3::new(2, FLOOR_Y + 1, 2)), block::SAND);
+    }
+
+    #[test]
+    fn sand_column_falls_and_stacks() {
+        let mut world = world_with_floor(FLOOR_Y, block::STONE);
+
+        for y in 0..3 {
+            world.update_block(IVec3::new(2, FLOOR_Y + 20 + y, 2), block::GRAVEL, 0);
+        }
+
+        run_ticks(&mut world, 200);
+
+        for y in 0..3 {
+            assert_eq!(world.get_global_block(IVec3::new(2, FLOOR_Y + 1 + y, 2)), block::GRAVEL);
+        }
+
+        assert_eq!(world.get_global_block(IVec3::new(2, FLOOR_Y + 4, 2)), block::AIR);
+    }
+
+    #[test]
+    fn sand_sinks_through_water() {
+        let mut world = world_with_floor(FLOOR_Y, block::STONE);
+        let water = IVec3::new(2, FLOOR_Y + 1, 2);
+
+        world.update_block(water, block::WATER, 0);
+        world.update_block(water + IVec3::Y * 3, block::SAND, 0);
+        run_ticks(&mut world, 100);
+
+        assert_eq!(world.get_global_block(water), block::SAND);
+    }
+}
@@ -0,0 +1,251 @@
+// SPDX-FileCopyrightText: © 2025 - 2026 Elias Steininger <elias.st4600@gmail.com> and Project Contributors (see CONTRIBUTORS.md)
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use crate::world::block;
+use crate::world::world::World;
+use glam::IVec3;
+
+// Level 0 is a source block, 1..=MAX_LEVEL is flowing fluid getting thinner the further it spreads
+pub const SOURCE_LEVEL: u8 = 0;
+pub const MAX_LEVEL: u8 = 7;
+pub const FALLING_LEVEL: u8 = 8; // Fluid pouring down from above, spreads like a source when it lands
+
+const WATER_TICK_DELAY: u64 = 5;
+const LAVA_TICK_DELAY: u64 = 30;
+
+const HORIZONTAL_NEIGHBORS: [IVec3; 4] = [IVec3::X, IVec3::NEG_X, IVec3::Z, IVec3::NEG_Z];
+
+/// World ticks between two flow updates of the given fluid
+pub fn get_tick_delay(id: u8) -> u64 {
+    if id == block::LAVA {LAVA_TICK_DELAY} else {WATER_TICK_DELAY}
+}
+
+/// How much the level decays per block of horizontal spread
+fn get_level_decay(id: u8) -> u8 {
+    if id == block::LAVA {2} else {1}
+}
+
+/// Distance from the nearest source, falling fluid counts as a source
+fn get_effective_level(level: u8) -> u8 {
+    if level >= FALLING_LEVEL {SOURCE_LEVEL} else {level}
+}
+
+/// Height of the fluid surface inside its block, from 0.0 to 1.0
+pub fn get_surface_height(level: u8) -> f32 {
+    (FALLING_LEVEL - get_effective_level(level)) as f32 / (FALLING_LEVEL + 1) as f32
+}
+
+/// Runs one scheduled flow update for the fluid at `pos`
+pub fn update_fluid(world: &mut World, pos: IVec3) {
+    let id = world.get_global_block(pos);
+
+    if !block::is_fluid(id) {
+        return;
+    }
+
+    let mut level = world.get_fluid_level(pos);
+
+    if id == block::LAVA && touches_water(world, pos) {
+        world.update_block(pos, if level == SOURCE_LEVEL {block::OBSIDIAN} else {block::COBBLESTONE}, 0);
+        return;
+    }
+
+    if level != SOURCE_LEVEL {
+        match get_fed_level(world, pos, id) {
+            None => {
+                world.update_block(pos, block::AIR, 0);
+                return;
+            }
+            Some(new_level) if new_level != level => {
+                world.update_block(pos, id, new_level);
+                level = new_level;
+            }
+            _ => {}
+        }
+    }
+
+    let below = pos - IVec3::Y;
+    let below_id = world.get_global_block(below);
+
+    if below.y >= 0 && (below_id == block::AIR || (below_id == id && world.get_fluid_level(below) != SOURCE_LEVEL)) {
+        if below_id == block::AIR || world.get_fluid_level(below) != FALLING_LEVEL {
+            world.update_block(below, id, FALLING_LEVEL);
+        }
+        return;
+    }
+
+    let spread_level = get_effective_level(level) + get_level_decay(id);
+
+    if spread_level > MAX_LEVEL {
+        return;
+    }
+
+    for offset in HORIZONTAL_NEIGHBORS {
+        let neighbor = pos + offset;
+        let neighbor_id = world.get_global_block(neighbor);
+
+        if neighbor_id == block::AIR || (neighbor_id == id && get_effective_level(world.get_fluid_level(neighbor)) > spread_level) {
+            world.update_block(neighbor, id, spread_level);
+        }
+    }
+}
+
+/// Level a flowing cell should have given its neighbours, None if nothing feeds it anymore
+fn get_fed_level(world: &World, pos: IVec3, id: u8) -> Option<u8> {
+    if world.get_global_block(pos + IVec3::Y) == id {
+        return Some(FALLING_LEVEL);
+    }
+
+    let mut best: Option<u8> = None;
+    let mut adjacent_sources = 0;
+
+    for offset in HORIZONTAL_NEIGHBORS {
+        let neighbor = pos + offset;
+
+        if world.get_global_block(neighbor) != id {
+            continue;
+        }
+
+        let neighbor_level = world.get_fluid_level(neighbor);
+
+        if neighbor_level == SOURCE_LEVEL {
+            adjacent_sources += 1;
+        }
+
+        let fed_level = get_effective_level(neighbor_level) + get_level_decay(id);
+
+        if fed_level <= MAX_LEVEL {
+            best = Some(best.map_or(fed_level, |best| best.min(fed_level)));
+        }
+    }
+
+    // Water between two sources on solid ground (or more water) becomes a source itself
+    if id == block::WATER && adjacent_sources >= 2 {
+        let below = pos - IVec3::Y;
+        let below_id = world.get_global_block(below);
+
+        if block::is_solid(below_id) || (below_id == id && world.get_fluid_level(below) == SOURCE_LEVEL) {
+            return Some(SOURCE_LEVEL);
+        }
+    }
+
+    best
+}
+
+fn touches_water(world: &World, pos: IVec3) -> bool {
+    HORIZONTAL_NEIGHBORS.iter().chain([IVec3::Y].iter()).any(|offset| world.get_global_block(pos + *offset) == block::WATER)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::test_util::{new_chunk, run_ticks, world_with_floor};
+    use glam::IVec2;
+
+    const FLOOR_Y: i32 = 10;
+
+    #[test]
+    fn water_spreads_with_decaying_level() {
+        let mut world = world_with_floor(FLOOR_Y, block::STONE);
+        let source = IVec3::new(0, FLOOR_Y + 1, 0);
+
+        world.update_block(source, block::WATER, SOURCE_LEVEL);
+        run_ticks(&mut world, 100);
+
+        for distance in 1..=MAX_LEVEL as i32 {
+            assert_eq!(world.get_global_block(source + IVec3::X * distance), block::WATER);
+            assert_eq!(world.get_fluid_level(source + IVec3::X * distance), distance as u8);
+        }
+
+        assert_eq!(world.get_global_block(source + IVec3::X * (MAX_LEVEL as i32 + 1)), block::AIR);
+    }
+
+    #[test]
+    fn water_drains_when_source_is_removed() {
+        let mut world = world_with_floor(FLOOR_Y, block::STONE);
+        let source = IVec3::new(0, FLOOR_Y + 1, 0);
+
+        world.update_block(source, block::WATER, SOURCE_LEVEL);
+        run_ticks(&mut world, 100);
+
+        world.update_block(source, block::AIR, 0);
+        run_ticks(&mut world, 200);
+
+        for distance in 0..=MAX_LEVEL as i32 {
+            assert_eq!(world.get_global_block(source + IVec3::X * distance), block::AIR);
+        }
+    }
+
+    #[test]
+    fn water_falls_before_spreading() {
+        let mut world = world_with_floor(FLOOR_Y, block::STONE);
+        let source = IVec3::new(0, FLOOR_Y + 5, 0);
+
+        world.update_block(source, block::WATER, SOURCE_LEVEL);
+        run_ticks(&mut world, 100);
+
+        for y in FLOOR_Y + 1..FLOOR_Y + 5 {
+            assert_eq!(world.get_global_block(IVec3::new(0, y, 0)), block::WATER);
+            assert_eq!(world.get_fluid_level(IVec3::new(0, y, 0)), FALLING_LEVEL);
+        }
+
+        assert_eq!(world.get_global_block(source + IVec3::X), block::AIR);
+        assert_eq!(world.get_fluid_level(IVec3::new(1, FLOOR_Y + 1, 0)), 1);
+    }
+
+    #[test]
+    fn two_sources_create_a_third() {
+        let mut world = world_with_floor(FLOOR_Y, block::STONE);
+
+        world.update_block(IVec3::new(0, FLOOR_Y + 1, 0), block::WATER, SOURCE_LEVEL);
+        world.update_block(IVec3::new(2, FLOOR_Y + 1, 0), block::WATER, SOURCE_LEVEL);
+        run_ticks(&mut world, 50);
+
+        assert_eq!(world.get_fluid_level(IVec3::new(1, FLOOR_Y + 1, 0)), SOURCE_LEVEL);
+    }
+
+    #[test]
+    fn lava_hardens_next_to_water() {
+        let mut world = world_with_floor(FLOOR_Y, block::STONE);
+        let lava = IVec3::new(0, FLOOR_Y + 1, 0);
+
+        world.update_block(lava, block::LAVA, SOURCE_LEVEL);
+        world.update_block(lava + IVec3::new(3, 0, 0), block::WATER, SOURCE_LEVEL);
+        run_ticks(&mut world, 200);
+
+        assert_eq!(world.get_global_block(lava), block::OBSIDIAN);
+    }
+
+    #[test]
+    fn generated_water_flows_once_loaded() {
+        let mut world = World::new();
+        let chunk_with_water = |pos: IVec2, water: &[IVec3]| {
+            let mut chunk = new_chunk(pos);
+
+            for x in 0..16 {
+                for z in 0..16 {
+                    chunk.set_block(IVec3::new(x, FLOOR_Y, z), block::STONE);
+                }
+            }
+
+            for pos in water {
+                chunk.set_block(*pos, block::WATER);
+            }
+
+            chunk
+        };
+
+        world.add_chunk(chunk_with_water(IVec2::ZERO, &[IVec3::new(8, FLOOR_Y + 1, 8), IVec3::new(0, FLOOR_Y + 1, 4)]));
+        run_ticks(&mut world, 20);
+
+        assert_eq!(world.get_global_block(IVec3::new(9, FLOOR_Y + 1, 8)), block::WATER);
+        assert_eq!(world.get_fluid_level(IVec3::new(9, FLOOR_Y + 1, 8)), 1);
+
+        // Water at the border flows into the neighbouring chunk once that is loaded
+        assert_eq!(world.get_global_block(IVec3::new(-1, FLOOR_Y + 1, 4)), block::AIR);
+        world.add_chunk(chunk_with_water(IVec2::NEG_X, &[]));
+        run_ticks(&mut world, 20);
+
+        assert_eq!(world.get_global_block(IVec3::new(-1, FLOOR_Y + 1, 4)), block::WATER);
+    }
+}
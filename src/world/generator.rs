@@ -0,0 +1,279 @@
+// SPDX-FileCopyrightText: © 2025 - 2026 Elias Steininger <elias.st4600@gmail.com> and Project Contributors (see CONTRIBUTORS.md)
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use crate::world::block;
+use crate::world::chunk::{Chunk, CHUNK_DIMENSION, CHUNK_HEIGHT};
+use crate::world::heightmap::HeightmapGenerator;
+use crate::world::terrain::TerrainGenerator;
+use glam::IVec3;
+use std::path::Path;
+
+const DEFAULT_SUPERFLAT_LAYERS: &str = "bedrock, 2*dirt, grass";
+const VOID_PLATFORM_HEIGHT: i32 = 64;
+const VOID_PLATFORM_RADIUS: i32 = 2; // The platform is 5x5 blocks around the spawn
+const DEBUG_HEIGHT: i32 = 64; // Height of the floor the blocks are laid out on
+const DEBUG_SPACING: i32 = 2; // Blocks are one block apart, so every face is visible
+
+/// Fills chunks with blocks when they are generated
+pub trait WorldGenerator {
+    /// Generates the chunk and returns blocks that belong to other chunks, in world coordinates
+    fn generate_chunk(&self, chunk: &mut Chunk) -> Vec<(IVec3, u8)>;
+
+    /// Height of the topmost block of the column, the player spawns above it
+    fn get_spawn_height(&self, x: i32, z: i32) -> i32;
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum GeneratorPreset {
+    Default,
+    Amplified,
+    Superflat,
+    Void,
+    Debug,
+    Heightmap
+}
+
+/// Options of the presets that need more than a seed
+#[derive(Clone)]
+pub struct GeneratorSettings {
+    pub superflat_layers: String,
+    pub heightmap_path: String,
+    pub heightmap_materials: bool
+}
+
+impl Default for GeneratorSettings {
+    fn default() -> Self {
+        GeneratorSettings {
+            superflat_layers: DEFAULT_SUPERFLAT_LAYERS.to_string(),
+            heightmap_path: String::new(),
+            heightmap_materials: true
+        }
+    }
+}
+
+impl GeneratorPreset {
+    pub const ALL: [GeneratorPreset; 6] = [GeneratorPreset::Default, GeneratorPreset::Amplified, GeneratorPreset::Superflat, GeneratorPreset::Void, GeneratorPreset::Debug, GeneratorPreset::Heightmap];
+
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            GeneratorPreset::Default => "Default",
+            GeneratorPreset::Amplified => "Amplified",
+            GeneratorPreset::Superflat => "Superflat",
+            GeneratorPreset::Void => "Void",
+            GeneratorPreset::Debug => "Debug",
+            GeneratorPreset::Heightmap => "Heightmap"
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<GeneratorPreset> {
+        Self::ALL.into_iter().find(|preset| preset.get_name() == name)
+    }
+
+    /// Creates the generator, `settings` are only used by the superflat and heightmap presets
+    pub fn create(&self, seed: u64, settings: &GeneratorSettings) -> Result<Box<dyn WorldGenerator>, String> {
+        Ok(match self {
+            GeneratorPreset::Default => Box::new(TerrainGenerator::new(seed)),
+            GeneratorPreset::Amplified => Box::new(TerrainGenerator::amplified(seed)),
+            GeneratorPreset::Superflat => Box::new(SuperflatGenerator::parse(&settings.superflat_layers)?),
+            GeneratorPreset::Void => Box::new(VoidGenerator),
+            GeneratorPreset::Debug => Box::new(DebugGenerator),
+            GeneratorPreset::Heightmap => Box::new(HeightmapGenerator::load(Path::new(&settings.heightmap_path), settings.heightmap_materials)?)
+        })
+    }
+}
+
+/// Flat world made of the same layers everywhere
+pub struct SuperflatGenerator {
+    layers: Vec<u8> // One block id per layer, from the bottom up
+}
+
+impl SuperflatGenerator {
+    /// Parses a comma separated list of layers from the bottom up, where `3*dirt` stands for three layers of dirt
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut layers = Vec::new();
+
+        for layer in text.split(',').map(str::trim).filter(|layer| !layer.is_empty()) {
+            let (count, name) = match layer.split_once('*') {
+                Some((count, name)) => (count.trim().parse::<usize>().map_err(|_| format!("Invalid layer count in '{layer}'"))?, name.trim()),
+                None => (1, layer)
+            };
+
+            let id = block::get_id(name).ok_or(format!("Unknown block '{name}'"))?;
+            layers.extend(std::iter::repeat_n(id, count));
+        }
+
+        if layers.is_empty() || layers.len() > CHUNK_HEIGHT as usize {
+            return Err(format!("A superflat world needs 1 to {CHUNK_HEIGHT} layers"));
+        }
+
+        Ok(SuperflatGenerator {layers})
+    }
+}
+
+impl WorldGenerator for SuperflatGenerator {
+    fn generate_chunk(&self, chunk: &mut Chunk) -> Vec<(IVec3, u8)> {
+        for x in 0..CHUNK_DIMENSION as i32 {
+            for z in 0..CHUNK_DIMENSION as i32 {
+                for (y, &id) in self.layers.iter().enumerate() {
+                    chunk.set_block(IVec3::new(x, y as i32, z), id);
+                }
+            }
+        }
+
+        Vec::new()
+    }
+
+    fn get_spawn_height(&self, _x: i32, _z: i32) -> i32 {
+        self.layers.len() as i32 - 1
+    }
+}
+
+/// Empty world with a small platform to spawn on
+pub struct VoidGenerator;
+
+impl WorldGenerator for VoidGenerator {
+    fn generate_chunk(&self, chunk: &mut Chunk) -> Vec<(IVec3, u8)> {
+        let origin = chunk.position * CHUNK_DIMENSION as i32;
+
+        for x in 0..CHUNK_DIMENSION as i32 {
+            for z in 0..CHUNK_DIMENSION as i32 {
+                if (origin.x + x).abs() <= VOID_PLATFORM_RADIUS && (origin.y + z).abs() <= VOID_PLATFORM_RADIUS {
+                    chunk.set_block(IVec3::new(x, VOID_PLATFORM_HEIGHT, z), block::STONE);
+                }
+            }
+        }
+
+        Vec::new()
+    }
+
+    fn get_spawn_height(&self, _x: i32, _z: i32) -> i32 {
+        VOID_PLATFORM_HEIGHT
+    }
+}
+
+/// Every block type laid out in a grid on a floor, for checking textures and shapes
+pub struct DebugGenerator;
+
+impl DebugGenerator {
+    /// Block shown at a world position of the grid, if any. Fluids are sunk into the floor, so they can't flow away.
+    fn get_block(x: i32, z: i32) -> Option<u8> {
+        let columns = (block::BLOCK_COUNT as f32).sqrt().ceil() as i32;
+
+        if x < 0 || z < 0 || x % DEBUG_SPACING != 0 || z % DEBUG_SPACING != 0 || x / DEBUG_SPACING >= columns {
+            return None;
+        }
+
+        let id = z / DEBUG_SPACING * columns + x / DEBUG_SPACING + 1; // Air is skipped
+
+        (id < block::BLOCK_COUNT as i32).then_some(id as u8)
+    }
+}
+
+impl WorldGenerator for DebugGenerator {
+    fn generate_chunk(&self, chunk: &mut Chunk) -> Vec<(IVec3, u8)> {
+        let origin = chunk.position * CHUNK_DIMENSION as i32;
+
+        for x in 0..CHUNK_DIMENSION as i32 {
+            for z in 0..CHUNK_DIMENSION as i32 {
+                chunk.set_block(IVec3::new(x, DEBUG_HEIGHT, z), block::STONE);
+
+                match Self::get_block(origin.x + x, origin.y + z) {
+                    Some(id) if block::is_fluid(id) => {
+                        chunk.set_block(IVec3::new(x, DEBUG_HEIGHT - 1, z), block::STONE);
+                        chunk.set_block(IVec3::new(x, DEBUG_HEIGHT, z), id);
+                    }
+                    Some(id) => chunk.set_block(IVec3::new(x, DEBUG_HEIGHT + 1, z), id),
+                    None => {}
+                }
+            }
+        }
+
+        Vec::new()
+    }
+
+    fn get_spawn_height(&self, _x: i32, _z: i32) -> i32 {
+        DEBUG_HEIGHT
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::test_util::{generate, get_dummy_shader, run_ticks};
+    use crate::world::world::World;
+    use glam::IVec2;
+
+    #[test]
+    fn superflat_stacks_layers_from_the_bottom() {
+        let generator = SuperflatGenerator::parse("bedrock, 3*stone, grass").unwrap();
+        let chunk = generate(&generator, IVec2::new(5, -3));
+
+        assert_eq!(chunk.get_block(IVec3::new(7, 0, 7)), block::BEDROCK);
+        assert_eq!(chunk.get_block(IVec3::new(7, 3, 7)), block::STONE);
+        assert_eq!(chunk.get_block(IVec3::new(7, 4, 7)), block::GRASS);
+        assert_eq!(chunk.get_block(IVec3::new(7, 5, 7)), block::AIR);
+        assert_eq!(generator.get_spawn_height(0, 0), 4);
+
+        assert!(SuperflatGenerator::parse("2*cheese").is_err());
+        assert!(SuperflatGenerator::parse("").is_err());
+    }
+
+    #[test]
+    fn void_only_has_the_spawn_platform() {
+        let origin = generate(&VoidGenerator, IVec2::ZERO);
+        let far = generate(&VoidGenerator, IVec2::new(3, 3));
+
+        assert_eq!(origin.get_block(IVec3::new(0, VOID_PLATFORM_HEIGHT, 0)), block::STONE);
+        assert_eq!(origin.get_block(IVec3::new(VOID_PLATFORM_RADIUS + 1, VOID_PLATFORM_HEIGHT, 0)), block::AIR);
+        assert!((0..CHUNK_HEIGHT as i32).all(|y| far.get_block(IVec3::new(0, y, 0)) == block::AIR));
+    }
+
+    #[test]
+    fn debug_world_shows_every_block() {
+        let mut found = [false; block::BLOCK_COUNT];
+
+        for chunk_x in 0..2 {
+            for chunk_z in 0..2 {
+                let chunk = generate(&DebugGenerator, IVec2::new(chunk_x, chunk_z));
+
+                for x in 0..CHUNK_DIMENSION as i32 {
+                    for z in 0..CHUNK_DIMENSION as i32 {
+                        found[chunk.get_block(IVec3::new(x, DEBUG_HEIGHT + 1, z)) as usize] = true;
+                        found[chunk.get_block(IVec3::new(x, DEBUG_HEIGHT, z)) as usize] = true;
+                    }
+                }
+            }
+        }
+
+        assert!(found[1..].iter().all(|&found| found));
+
+        // Nothing in the grid flows or falls once the world runs. Random ticks still decay the lone leaves and grow the sapling.
+        let mut world = World::new();
+
+        for chunk_x in -1..=1 {
+            for chunk_z in -1..=1 {
+                world.insert_chunk(IVec2::new(chunk_x, chunk_z), get_dummy_shader(), &DebugGenerator);
+            }
+        }
+
+        run_ticks(&mut world, 400);
+
+        let chunk = generate(&DebugGenerator, IVec2::ZERO);
+
+        for x in -1..=CHUNK_DIMENSION as i32 {
+            for z in -1..=CHUNK_DIMENSION as i32 {
+                for y in DEBUG_HEIGHT - 1..=DEBUG_HEIGHT + 2 {
+                    let inside = (0..CHUNK_DIMENSION as i32).contains(&x) && (0..CHUNK_DIMENSION as i32).contains(&z);
+                    let expected = if inside {chunk.get_block(IVec3::new(x, y, z))} else if y == DEBUG_HEIGHT {block::STONE} else {block::AIR};
+
+                    // A grown sapling also puts its trunk into the air above it
+                    if inside && y > DEBUG_HEIGHT && matches!(chunk.get_block(IVec3::new(x, DEBUG_HEIGHT + 1, z)), block::LEAVES | block::SAPLING) {
+                        continue;
+                    }
+
+                    assert_eq!(world.get_global_block(IVec3::new(x, y, z)), expected, "at {x} {y} {z}");
+                }
+            }
+        }
+    }
+}
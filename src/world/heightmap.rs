@@ -0,0 +1,176 @@
+// SPDX-FileCopyrightText: © 2025 - 2026 Elias Steininger <elias.st4600@gmail.com> and Project Contributors (see CONTRIBUTORS.md)
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use crate::world::block;
+use crate::world::chunk::{Chunk, CHUNK_DIMENSION, CHUNK_HEIGHT};
+use crate::world::generator::WorldGenerator;
+use crate::world::terrain::SEA_LEVEL;
+use glam::IVec3;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+
+const MIN_HEIGHT: i32 = 1; // Black pixels, one block above the bedrock
+const MAX_HEIGHT: i32 = CHUNK_HEIGHT as i32 - 4; // White pixels, low enough for the player to stand on top
+const FILLER_DEPTH: i32 = 3;
+const STEEP_SLOPE: i32 = 3; // Columns this much higher or lower than a neighbour are bare stone
+const SNOW_HEIGHT: i32 = 170;
+
+/// Terrain from a grayscale image, where every pixel is one column and its brightness the height.
+/// The image is centered on the world origin, outside of it the edge pixels continue.
+pub struct HeightmapGenerator {
+    width: i32,
+    depth: i32,
+    heights: Vec<i32>,
+    materials: bool // Picks surface blocks by height and slope and fills the sea, otherwise everything is grass
+}
+
+impl HeightmapGenerator {
+    /// Loads a PNG file, colored images are converted to grayscale
+    pub fn load(path: &Path, materials: bool) -> Result<Self, String> {
+        let file = File::open(path).map_err(|error| format!("Failed to open '{}': {error}", path.display()))?;
+        let mut decoder = png::Decoder::new(BufReader::new(file));
+        decoder.set_transformations(png::Transformations::normalize_to_color8());
+
+        let mut reader = decoder.read_info().map_err(|error| format!("Invalid PNG: {error}"))?;
+        let mut buffer = vec![0; reader.output_buffer_size().ok_or("PNG is too large")?];
+        let info = reader.next_frame(&mut buffer).map_err(|error| format!("Invalid PNG: {error}"))?;
+        let channels = info.color_type.samples();
+
+        // Average the color channels, ignoring alpha
+        let color_channels = if channels >= 3 {3} else {1};
+        let brightness: Vec<u8> = buffer[..info.buffer_size()].chunks(channels).map(|pixel| {
+            (pixel[..color_channels].iter().map(|&value| value as u32).sum::<u32>() / color_channels as u32) as u8
+        }).collect();
+
+        Ok(Self::from_brightness(info.width as i32, info.height as i32, &brightness, materials))
+    }
+
+    /// Maps one brightness value per column, row by row, from `MIN_HEIGHT` to `MAX_HEIGHT`
+    pub fn from_brightness(width: i32, depth: i32, brightness: &[u8], materials: bool) -> Self {
+        HeightmapGenerator {
+            width,
+            depth,
+            heights: brightness.iter().map(|&value| MIN_HEIGHT + value as i32 * (MAX_HEIGHT - MIN_HEIGHT) / 255).collect(),
+            materials
+        }
+    }
+
+    pub fn get_height(&self, x: i32, z: i32) -> i32 {
+        let image_x = (x + self.width / 2).clamp(0, self.width - 1);
+        let image_z = (z + self.depth / 2).clamp(0, self.depth - 1);
+
+        self.heights[(image_z * self.width + image_x) as usize]
+    }
+
+    /// Surface and filler block of a column
+    fn get_surface_blocks(&self, x: i32, z: i32, height: i32) -> (u8, u8) {
+        if !self.materials {
+            return (block::GRASS, block::DIRT);
+        }
+
+        let slope = [(1, 0), (-1, 0), (0, 1), (0, -1)].iter().map(|&(dx, dz)| (self.get_height(x + dx, z + dz) - height).abs()).max().unwrap();
+
+        if height <= SEA_LEVEL + 1 {
+            (block::SAND, block::SAND)
+        } else if slope >= STEEP_SLOPE {
+            (block::STONE, block::STONE)
+        } else if height >= SNOW_HEIGHT {
+            (block::SNOW, block::DIRT)
+        } else {
+            (block::GRASS, block::DIRT)
+        }
+    }
+}
+
+impl WorldGenerator for HeightmapGenerator {
+    fn generate_chunk(&self, chunk: &mut Chunk) -> Vec<(IVec3, u8)> {
+        let origin = chunk.position * CHUNK_DIMENSION as i32;
+
+        for x in 0..CHUNK_DIMENSION as i32 {
+            for z in 0..CHUNK_DIMENSION as i32 {
+                let (world_x, world_z) = (origin.x + x, origin.y + z);
+                let height = self.get_height(world_x, world_z);
+                let (surface, filler) = self.get_surface_blocks(world_x, world_z, height);
+
+                chunk.set_block(IVec3::new(x, 0, z), block::BEDROCK);
+
+                for y in MIN_HEIGHT..=height {
+                    let id = if y == height {surface} else if y > height - FILLER_DEPTH {filler} else {block::STONE};
+
+                    chunk.set_block(IVec3::new(x, y, z), id);
+                }
+
+                if self.materials {
+                    for y in height + 1..=SEA_LEVEL {
+                        chunk.set_block(IVec3::new(x, y, z), block::WATER);
+                    }
+                }
+            }
+        }
+
+        Vec::new()
+    }
+
+    fn get_spawn_height(&self, x: i32, z: i32) -> i32 {
+        self.get_height(x, z)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::test_util::{generate, get_temp_path};
+    use glam::IVec2;
+    use std::io::BufWriter;
+
+    #[test]
+    fn brightness_maps_to_height() {
+        // A 2x2 image centered on the origin covers the columns -1 and 0
+        let generator = HeightmapGenerator::from_brightness(2, 2, &[0, 255, 128, 128], false);
+
+        assert_eq!(generator.get_height(-1, -1), MIN_HEIGHT);
+        assert_eq!(generator.get_height(0, -1), MAX_HEIGHT);
+        assert_eq!(generator.get_height(50, -50), MAX_HEIGHT);
+        assert!(generator.get_spawn_height(0, -1) + 3 <= CHUNK_HEIGHT as i32, "no room for the player above the highest column");
+
+        let chunk = generate(&generator, IVec2::ZERO);
+        let height = generator.get_height(0, 0);
+
+        assert_eq!(chunk.get_block(IVec3::new(0, 0, 0)), block::BEDROCK);
+        assert_eq!(chunk.get_block(IVec3::new(0, height, 0)), block::GRASS);
+        assert_eq!(chunk.get_block(IVec3::new(0, height + 1, 0)), block::AIR);
+    }
+
+    #[test]
+    fn materials_follow_height_and_slope() {
+        let low = (SEA_LEVEL - 10) * 255 / (MAX_HEIGHT - MIN_HEIGHT);
+        let generator = HeightmapGenerator::from_brightness(3, 1, &[0, 200, low as u8], true);
+        let chunk = generate(&generator, IVec2::ZERO);
+
+        // Column 0 is a cliff next to the sea that covers the low columns after it
+        assert_eq!(chunk.get_block(IVec3::new(0, generator.get_height(0, 0), 0)), block::STONE);
+        assert_eq!(chunk.get_block(IVec3::new(5, generator.get_height(5, 0), 0)), block::SAND);
+        assert_eq!(chunk.get_block(IVec3::new(5, SEA_LEVEL, 0)), block::WATER);
+
+        let mountain = generate(&HeightmapGenerator::from_brightness(1, 1, &[255], true), IVec2::ZERO);
+        assert_eq!(mountain.get_block(IVec3::new(5, MAX_HEIGHT, 5)), block::SNOW);
+    }
+
+    #[test]
+    fn loads_grayscale_png() {
+        let path = get_temp_path("heightmap.png");
+        let mut encoder = png::Encoder::new(BufWriter::new(File::create(&path).unwrap()), 2, 1);
+        encoder.set_color(png::ColorType::Grayscale);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder.write_header().unwrap().write_image_data(&[0, 255]).unwrap();
+
+        let generator = HeightmapGenerator::load(&path, false).unwrap();
+
+        assert_eq!(generator.get_height(-1, 0), MIN_HEIGHT);
+        assert_eq!(generator.get_height(0, 0), MAX_HEIGHT);
+        assert!(HeightmapGenerator::load(Path::new("does_not_exist.png"), false).is_err());
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,336 @@
+// SPDX-FileCopyrightText: © 2025 - 2026 Elias Steininger <elias.st4600@gmail.com> and Project Contributors (see CONTRIBUTORS.md)
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use crate::world::block;
+use crate::world::block::BlockCategory;
+
+pub const HOTBAR_SIZE: usize = 9;
+pub const MAX_STACK_SIZE: u8 = 64;
+
+const HAND_SPEED: f32 = 1.0;
+const HARVEST_PENALTY: f32 = 5.0 / 1.5; // Breaking a block without the required tier is slower
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ToolKind {
+    Pickaxe,
+    Axe,
+    Shovel
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum ToolTier {
+    Wood,
+    Stone,
+    Iron,
+    Diamond
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Tool {
+    pub kind: ToolKind,
+    pub tier: ToolTier,
+    durability: u16
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Item {
+    Block(u8),
+    Tool(Tool)
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ItemStack {
+    pub item: Item,
+    pub count: u8
+}
+
+pub struct Inventory {
+    slots: [Option<ItemStack>; HOTBAR_SIZE]
+}
+
+impl ToolKind {
+    pub const ALL: [ToolKind; 3] = [ToolKind::Pickaxe, ToolKind::Axe, ToolKind::Shovel];
+
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            ToolKind::Pickaxe => "pickaxe",
+            ToolKind::Axe => "axe",
+            ToolKind::Shovel => "shovel"
+        }
+    }
+
+    pub fn get_effective_category(&self) -> BlockCategory {
+        match self {
+            ToolKind::Pickaxe => BlockCategory::Stone,
+            ToolKind::Axe => BlockCategory::Wood,
+            ToolKind::Shovel => BlockCategory::Soil
+        }
+    }
+}
+
+impl ToolTier {
+    pub const ALL: [ToolTier; 4] = [ToolTier::Wood, ToolTier::Stone, ToolTier::Iron, ToolTier::Diamond];
+
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            ToolTier::Wood => "wooden",
+            ToolTier::Stone => "stone",
+            ToolTier::Iron => "iron",
+            ToolTier::Diamond => "diamond"
+        }
+    }
+
+    pub fn get_speed(&self) -> f32 {
+        match self {
+            ToolTier::Wood => 2.0,
+            ToolTier::Stone => 4.0,
+            ToolTier::Iron => 6.0,
+            ToolTier::Diamond => 8.0
+        }
+    }
+
+    pub fn get_max_durability(&self) -> u16 {
+        match self {
+            ToolTier::Wood => 60,
+            ToolTier::Stone => 132,
+            ToolTier::Iron => 251,
+            ToolTier::Diamond => 1562
+        }
+    }
+}
+
+impl Tool {
+    pub fn new(kind: ToolKind, tier: ToolTier) -> Self {
+        Tool {
+            kind,
+            tier,
+            durability: tier.get_max_durability()
+        }
+    }
+
+    /// A used tool, for example one loaded from a save
+    pub fn with_durability(kind: ToolKind, tier: ToolTier, durability: u16) -> Self {
+        Tool {
+            kind,
+            tier,
+            durability: durability.min(tier.get_max_durability())
+        }
+    }
+
+    pub fn get_durability(&self) -> u16 {self.durability}
+
+    pub fn is_effective_on(&self, block_id: u8) -> bool {
+        block::get_properties(block_id).category == self.kind.get_effective_category()
+    }
+
+    /// Returns true once the tool is used up and should be removed
+    pub fn wear(&mut self) -> bool {
+        self.durability = self.durability.saturating_sub(1);
+        self.durability == 0
+    }
+}
+
+impl Item {
+    pub fn get_name(&self) -> String {
+        match self {
+            Item::Block(id) => block::get_properties(*id).name.replace('_', " "),
+            Item::Tool(tool) => format!("{} {}", tool.tier.get_name(), tool.kind.get_name())
+        }
+    }
+}
+
+impl ItemStack {
+    pub fn new(item: Item, count: u8) -> Self {
+        ItemStack {item, count}
+    }
+
+    pub fn get_max_count(&self) -> u8 {
+        match self.item {
+            Item::Block(_) => MAX_STACK_SIZE,
+            Item::Tool(_) => 1
+        }
+    }
+}
+
+impl Inventory {
+    pub fn new() -> Self {
+        let mut inventory = Inventory {
+            slots: [None; HOTBAR_SIZE]
+        };
+
+        inventory.slots[0] = Some(ItemStack::new(Item::Tool(Tool::new(ToolKind::Pickaxe, ToolTier::Wood)), 1));
+        inventory.slots[1] = Some(ItemStack::new(Item::Tool(Tool::new(ToolKind::Shovel, ToolTier::Wood)), 1));
+        inventory.slots[2] = Some(ItemStack::new(Item::Tool(Tool::new(ToolKind::Axe, ToolTier::Wood)), 1));
+        inventory.slots[3] = Some(ItemStack::new(Item::Block(block::BRICKS), MAX_STACK_SIZE));
+
+        inventory
+    }
+
+    pub fn get_slot(&self, index: usize) -> Option<&ItemStack> {
+        self.slots.get(index).and_then(|slot| slot.as_ref())
+    }
+
+    pub fn get_slot_mut(&mut self, index: usize) -> Option<&mut ItemStack> {
+        self.slots.get_mut(index).and_then(|slot| slot.as_mut())
+    }
+
+    pub fn set_slot(&mut self, index: usize, stack: Option<ItemStack>) {
+        if let Some(slot) = self.slots.get_mut(index) {
+            *slot = stack;
+        }
+    }
+
+    pub fn clear_slot(&mut self, index: usize) {
+        if let Some(slot) = self.slots.get_mut(index) {
+            *slot = None;
+        }
+    }
+
+    /// Adds the item to a matching stack or the first free slot, returns false if the inventory is full
+    pub fn add_item(&mut self, item: Item) -> bool {
+        for stack in self.slots.iter_mut().flatten() {
+            if stack.item == item && stack.count < stack.get_max_count() {
+                stack.count += 1;
+                return true;
+            }
+        }
+
+        if let Some(slot) = self.slots.iter_mut().find(|slot| slot.is_none()) {
+            *slot = Some(ItemStack::new(item, 1));
+            return true;
+        }
+
+        false
+    }
+
+    /// Removes one item from the given slot, clearing it when the stack is empty
+    pub fn take_one(&mut self, index: usize) {
+        if let Some(stack) = self.get_slot_mut(index) {
+            stack.count -= 1;
+
+            if stack.count == 0 {
+                self.clear_slot(index);
+            }
+        }
+    }
+}
+
+/// Time in seconds it takes to break the given block with the given tool (or bare hand)
+pub fn get_break_time(block_id: u8, tool: Option<&Tool>) -> f32 {
+    let properties = block::get_properties(block_id);
+
+    if properties.hardness < 0.0 {
+        return f32::INFINITY;
+    }
+
+    let speed = match tool {
+        Some(tool) if tool.is_effective_on(block_id) => tool.tier.get_speed(),
+        _ => HAND_SPEED
+    };
+
+    let penalty = if can_harvest(block_id, tool) {1.0} else {HARVEST_PENALTY};
+
+    properties.hardness * 1.5 * penalty / speed
+}
+
+/// Whether breaking the block with the given tool yields a drop
+pub fn can_harvest(block_id: u8, tool: Option<&Tool>) -> bool {
+    match block::get_properties(block_id).required_tier {
+        None => true,
+        Some(required_tier) => match tool {
+            Some(tool) => tool.is_effective_on(block_id) && tool.tier >= required_tier,
+            None => false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn empty_inventory() -> Inventory {
+        Inventory {
+            slots: [None; HOTBAR_SIZE]
+        }
+    }
+
+    #[test]
+    fn right_tool_breaks_faster() {
+        let wooden_pickaxe = Tool::new(ToolKind::Pickaxe, ToolTier::Wood);
+        let diamond_pickaxe = Tool::new(ToolKind::Pickaxe, ToolTier::Diamond);
+        let shovel = Tool::new(ToolKind::Shovel, ToolTier::Diamond);
+
+        assert!((get_break_time(block::DIRT, None) - 0.75).abs() < 1e-6);
+        assert!(get_break_time(block::STONE, Some(&diamond_pickaxe)) < get_break_time(block::STONE, Some(&wooden_pickaxe)));
+        assert!((get_break_time(block::STONE, Some(&shovel)) - get_break_time(block::STONE, None)).abs() < 1e-6);
+        assert!(get_break_time(block::STONE, None) > 1.5 * 1.5); // Slowed down by the missing tier
+        assert_eq!(get_break_time(block::BEDROCK, Some(&diamond_pickaxe)), f32::INFINITY);
+    }
+
+    #[test]
+    fn drops_need_the_required_tier() {
+        let wooden_pickaxe = Tool::new(ToolKind::Pickaxe, ToolTier::Wood);
+        let iron_pickaxe = Tool::new(ToolKind::Pickaxe, ToolTier::Iron);
+        let diamond_axe = Tool::new(ToolKind::Axe, ToolTier::Diamond);
+
+        assert!(can_harvest(block::DIRT, None));
+        assert!(!can_harvest(block::STONE, None));
+        assert!(can_harvest(block::STONE, Some(&wooden_pickaxe)));
+        assert!(!can_harvest(block::STONE, Some(&diamond_axe)));
+        assert!(!can_harvest(block::DIAMOND_ORE, Some(&wooden_pickaxe)));
+        assert!(can_harvest(block::DIAMOND_ORE, Some(&iron_pickaxe)));
+    }
+
+    #[test]
+    fn tools_wear_out() {
+        let mut tool = Tool::new(ToolKind::Axe, ToolTier::Wood);
+
+        for _ in 1..ToolTier::Wood.get_max_durability() {
+            assert!(!tool.wear());
+        }
+
+        assert!(tool.wear());
+        assert_eq!(tool.get_durability(), 0);
+        assert_eq!(Tool::with_durability(ToolKind::Axe, ToolTier::Stone, u16::MAX).get_durability(), ToolTier::Stone.get_max_durability());
+    }
+
+    #[test]
+    fn items_stack_up_to_their_limit() {
+        let mut inventory = empty_inventory();
+        let pickaxe = Item::Tool(Tool::new(ToolKind::Pickaxe, ToolTier::Wood));
+
+        for _ in 0..MAX_STACK_SIZE + 1 {
+            assert!(inventory.add_item(Item::Block(block::DIRT)));
+        }
+
+        assert!(inventory.add_item(pickaxe));
+        assert!(inventory.add_item(pickaxe));
+
+        assert_eq!(inventory.get_slot(0), Some(&ItemStack::new(Item::Block(block::DIRT), MAX_STACK_SIZE)));
+        assert_eq!(inventory.get_slot(1), Some(&ItemStack::new(Item::Block(block::DIRT), 1)));
+        assert_eq!(inventory.get_slot(2), Some(&ItemStack::new(pickaxe, 1)));
+        assert_eq!(inventory.get_slot(3), Some(&ItemStack::new(pickaxe, 1)));
+
+        for i in 4..HOTBAR_SIZE {
+            inventory.set_slot(i, Some(ItemStack::new(pickaxe, 1)));
+        }
+
+        assert!(!inventory.add_item(Item::Block(block::STONE)));
+        assert!(inventory.add_item(Item::Block(block::DIRT)));
+    }
+
+    #[test]
+    fn taking_the_last_item_clears_the_slot() {
+        let mut inventory = empty_inventory();
+        inventory.set_slot(4, Some(ItemStack::new(Item::Block(block::SAND), 2)));
+
+        inventory.take_one(4);
+        assert_eq!(inventory.get_slot(4).map(|stack| stack.count), Some(1));
+
+        inventory.take_one(4);
+        assert_eq!(inventory.get_slot(4), None);
+
+        inventory.take_one(HOTBAR_SIZE); // Out of range slots are ignored
+        assert_eq!(inventory.get_slot(HOTBAR_SIZE), None);
+    }
+}
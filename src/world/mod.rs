@@ -1,3 +1,21 @@
 pub mod chunk;
+#[allow(clippy::module_inception)]
 pub mod world;
-pub mod player;
\ No newline at end of file
+pub mod player;
+pub mod block;
+pub mod item;
+pub mod collision;
+pub mod fluid;
+pub mod random_tick;
+pub mod falling_block;
+pub mod biome;
+pub mod terrain;
+pub mod noise;
+pub mod ore;
+pub mod decoration;
+pub mod structure;
+pub mod generator;
+pub mod heightmap;
+pub mod save;
+#[cfg(test)]
+pub mod test_util;
\ No newline at end of file
@@ -0,0 +1,83 @@
+// SPDX-FileCopyrightText: © 2025 - 2026 Elias Steininger <elias.st4600@gmail.com> and Project Contributors (see CONTRIBUTORS.md)
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+
+/// Seeded 3D gradient noise (improved Perlin noise), the worldgen crate only provides 2D noise
+pub struct Noise3D {
+    permutation: [u8; 512]
+}
+
+impl Noise3D {
+    pub fn new(seed: u64) -> Self {
+        let mut values: Vec<u8> = (0..=255).collect();
+        values.shuffle(&mut StdRng::seed_from_u64(seed));
+
+        let mut permutation = [0; 512];
+
+        for (i, value) in permutation.iter_mut().enumerate() {
+            *value = values[i % 256];
+        }
+
+        Noise3D {permutation}
+    }
+
+    /// Noise value at the given position, roughly in -1.0..1.0 and 0.0 at every integer coordinate
+    pub fn sample(&self, x: f64, y: f64, z: f64) -> f64 {
+        let (cell_x, cell_y, cell_z) = (x.floor(), y.floor(), z.floor());
+        let (x, y, z) = (x - cell_x, y - cell_y, z - cell_z);
+        let (cell_x, cell_y, cell_z) = ((cell_x as i64 & 255) as usize, (cell_y as i64 & 255) as usize, (cell_z as i64 & 255) as usize);
+
+        let (u, v, w) = (fade(x), fade(y), fade(z));
+        let p = &self.permutation;
+
+        let a = p[cell_x] as usize + cell_y;
+        let aa = p[a] as usize + cell_z;
+        let ab = p[a + 1] as usize + cell_z;
+        let b = p[cell_x + 1] as usize + cell_y;
+        let ba = p[b] as usize + cell_z;
+        let bb = p[b + 1] as usize + cell_z;
+
+        lerp(w,
+            lerp(v,
+                lerp(u, gradient(p[aa], x, y, z), gradient(p[ba], x - 1.0, y, z)),
+                lerp(u, gradient(p[ab], x, y - 1.0, z), gradient(p[bb], x - 1.0, y - 1.0, z))),
+            lerp(v,
+                lerp(u, gradient(p[aa + 1], x, y, z - 1.0), gradient(p[ba + 1], x - 1.0, y, z - 1.0)),
+                lerp(u, gradient(p[ab + 1], x, y - 1.0, z - 1.0), gradient(p[bb + 1], x - 1.0, y - 1.0, z - 1.0))))
+    }
+
+    /// Sum of `octaves` layers, each with double the frequency and half the amplitude of the previous one
+    pub fn fractal(&self, x: f64, y: f64, z: f64, octaves: u32) -> f64 {
+        let mut value = 0.0;
+        let mut frequency = 1.0;
+        let mut amplitude = 1.0;
+
+        for _ in 0..octaves {
+            value += self.sample(x * frequency, y * frequency, z * frequency) * amplitude;
+            frequency *= 2.0;
+            amplitude *= 0.5;
+        }
+
+        value
+    }
+}
+
+fn fade(t: f64) -> f64 {
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+fn lerp(t: f64, a: f64, b: f64) -> f64 {
+    a + t * (b - a)
+}
+
+/// Dot product of the offset with one of 12 gradient directions picked by the hash
+fn gradient(hash: u8, x: f64, y: f64, z: f64) -> f64 {
+    let h = hash & 15;
+    let u = if h < 8 {x} else {y};
+    let v = if h < 4 {y} else if h == 12 || h == 14 {x} else {z};
+
+    (if h & 1 == 0 {u} else {-u}) + (if h & 2 == 0 {v} else {-v})
+}
@@ -0,0 +1,54 @@
+// SPDX-FileCopyrightText: © 2025 - 2026 Elias Steininger <elias.st4600@gmail.com> and Project Contributors (see CONTRIBUTORS.md)
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use crate::world::block;
+use crate::world::chunk::{Chunk, CHUNK_DIMENSION};
+use glam::IVec3;
+use rand::{Rng, RngExt};
+
+pub struct OreProperties {
+    pub id: u8,
+    pub min_height: i32,
+    pub max_height: i32,
+    pub vein_size: u32, // Maximum number of blocks in one vein
+    pub veins_per_chunk: u32
+}
+
+pub const ORES: [OreProperties; 4] = [
+    OreProperties {id: block::COAL_ORE, min_height: 5, max_height: 130, vein_size: 12, veins_per_chunk: 20},
+    OreProperties {id: block::IRON_ORE, min_height: 5, max_height: 64, vein_size: 8, veins_per_chunk: 12},
+    OreProperties {id: block::GOLD_ORE, min_height: 5, max_height: 32, vein_size: 6, veins_per_chunk: 3},
+    OreProperties {id: block::DIAMOND_ORE, min_height: 5, max_height: 16, vein_size: 5, veins_per_chunk: 1}
+];
+
+const DIRECTIONS: [IVec3; 6] = [IVec3::X, IVec3::NEG_X, IVec3::Y, IVec3::NEG_Y, IVec3::Z, IVec3::NEG_Z];
+
+/// Places the veins of every ore into the stone of a generated chunk
+pub fn place_ores(chunk: &mut Chunk, rng: &mut impl Rng) {
+    for ore in &ORES {
+        for _ in 0..ore.veins_per_chunk {
+            let start = IVec3::new(
+                rng.random_range(0..CHUNK_DIMENSION as i32),
+                rng.random_range(ore.min_height..=ore.max_height),
+                rng.random_range(0..CHUNK_DIMENSION as i32)
+            );
+
+            place_vein(chunk, ore, start, rng);
+        }
+    }
+}
+
+/// Random walk from the start position, only replacing stone and never leaving the chunk or the ore's height range
+fn place_vein(chunk: &mut Chunk, ore: &OreProperties, start: IVec3, rng: &mut impl Rng) {
+    let mut pos = start;
+
+    for _ in 0..ore.vein_size {
+        let in_chunk = (0..CHUNK_DIMENSION as i32).contains(&pos.x) && (0..CHUNK_DIMENSION as i32).contains(&pos.z);
+
+        if in_chunk && (ore.min_height..=ore.max_height).contains(&pos.y) && chunk.get_block(pos) == block::STONE {
+            chunk.set_block(pos, ore.id);
+        }
+
+        pos += DIRECTIONS[rng.random_range(0..DIRECTIONS.len())];
+    }
+}
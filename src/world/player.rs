@@ -1,54 +1,179 @@
 // SPDX-FileCopyrightText: © 2025 - 2026 Elias Steininger <elias.st4600@gmail.com> and Project Contributors (see CONTRIBUTORS.md)
 // SPDX-License-Identifier: GPL-3.0-or-later
 
+use crate::input::Action;
+use crate::world::block;
+use crate::world::collision;
+use crate::world::collision::Aabb;
+use crate::world::item::Inventory;
 use crate::world::world::World;
-use glam::{IVec3, Vec3};
+use glam::{BVec3, IVec3, Vec2, Vec3};
 use std::collections::HashSet;
-use winit::keyboard::KeyCode;
 
 const PLAYER_HEIGHT: f32 = 1.8;
-const MOUSE_SENS: f32 = 0.04;
+const PLAYER_WIDTH: f32 = 0.6;
+const SNEAK_EYE_HEIGHT: f32 = PLAYER_HEIGHT - 0.3;
+const MOUSE_SENS: f32 = 0.04; // Degrees per pixel, scaled by the sensitivity setting
 const GRAVITY: u8 = 23;
 const JUMP_STRENGTH: u8 = 8;
-const SPEED: u8 = 4;
-const SPRINT_SPEED: u8 = 6;
+const SPEED: f32 = 4.0;
+const SPRINT_SPEED: f32 = 6.0;
+const SNEAK_SPEED: f32 = 1.3;
+const STEP_HEIGHT: f32 = 0.6; // Default for how high obstacles can be to be climbed without jumping, full blocks still need a jump
+const LEDGE_DEPTH: f32 = 0.6; // Drops deeper than this count as a ledge while sneaking
+const LEDGE_STEP: f32 = 0.05;
+const SWIM_SPEED_FACTOR: f32 = 0.5;
+const SWIM_UP_SPEED: f32 = 3.0;
+const BUOYANCY: f32 = 19.0; // Counteracts most of the gravity while in water
+const WATER_DRAG: f32 = 3.0;
+const MAX_SINK_SPEED: f32 = 2.0;
+const MAX_BREATH: f32 = 10.0; // Seconds the player can stay underwater
+const BREATH_REFILL_SPEED: f32 = 5.0;
+const DROWN_DAMAGE_INTERVAL: f32 = 1.0;
+const LAVA_DAMAGE_INTERVAL: f32 = 0.5;
+pub const MAX_HEALTH: u8 = 8;
+const VOID_DEATH_HEIGHT: f32 = -64.0; // Falling this far below the world kills the player
+const FLY_SPEED: f32 = 10.0;
+const FLY_SPRINT_SPEED: f32 = 20.0;
+const FLY_VERTICAL_SPEED: f32 = 7.5; // Rising with Jump and sinking with Sneak while flying
+const DOUBLE_TAP_TIME: f32 = 0.3; // Seconds between two Jump presses that toggle flying in creative
+
+/// How the player interacts with the world
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum GameMode {
+    Survival,
+    Creative, // No damage, flying, instant breaking and infinite blocks
+    Spectator // Always flying through blocks, can't interact with the world
+}
+
+impl GameMode {
+    pub const ALL: [GameMode; 3] = [GameMode::Survival, GameMode::Creative, GameMode::Spectator];
+
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            GameMode::Survival => "survival",
+            GameMode::Creative => "creative",
+            GameMode::Spectator => "spectator"
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<GameMode> {
+        GameMode::ALL.into_iter().find(|mode| mode.get_name() == name)
+    }
+}
 
 pub struct Player {
     pos: Vec3,
+    prev_pos: Vec3, // Position at the start of the last tick, used to interpolate rendering
     camera_front: Vec3,
     yaw: f32,
     pitch: f32,
     vertical_velocity: f32,
     was_grounded: bool,
-    health: u8
+    is_sneaking: bool,
+    step_height: f32, // Obstacles up to this height are climbed without jumping
+    in_water: bool,
+    head_in_water: bool,
+    breath: f32,
+    drown_timer: f32,
+    burn_timer: f32,
+    health: u8,
+    inventory: Inventory,
+    game_mode: GameMode,
+    flying: bool,
+    jump_held: bool, // Jump was active last tick, to find new presses
+    time_since_jump_press: f32,
+    spawn_point: Option<IVec3> // Where the player respawns instead of the world spawn, the block the feet are in
 }
 
 impl Player {
     pub fn new() -> Self {
         Player {
-            pos: Vec3::new(2.0, 125.0, 2.0),
+            pos: Vec3::ZERO, // Placed at the world spawn once the world is loaded
+            prev_pos: Vec3::ZERO,
             camera_front: Vec3::new(0.0, 0.0, -1.0),
             yaw: -90.0,
             pitch: 0.0,
             vertical_velocity: 0.0,
             was_grounded: false,
-            health: 8
+            is_sneaking: false,
+            step_height: STEP_HEIGHT,
+            in_water: false,
+            head_in_water: false,
+            breath: MAX_BREATH,
+            drown_timer: 0.0,
+            burn_timer: 0.0,
+            health: MAX_HEALTH,
+            inventory: Inventory::new(),
+            game_mode: GameMode::Survival,
+            flying: false,
+            jump_held: false,
+            time_since_jump_press: DOUBLE_TAP_TIME,
+            spawn_point: None
         }
     }
 
-    pub fn update_pos(&mut self, delta_time: f32, keys_pressed: HashSet<KeyCode>, world: &World) {
+    /// Runs one fixed simulation step of `delta_time` seconds, `stick` is analog movement with forward as positive y
+    pub fn update_pos(&mut self, delta_time: f32, actions: &HashSet<Action>, stick: Vec2, world: &World) {
+        self.prev_pos = self.pos;
 
-        if delta_time >= 0.3 { return; } // On game begin delta_time can be very big, which leads to weird behavior
+        self.update_flight_toggle(delta_time, actions.contains(&Action::Jump));
 
-        let is_grounded: bool = self.is_block_at(IVec3::new(self.pos.x.floor() as i32, self.pos.y.floor() as i32, self.pos.z.floor() as i32), &world);
-        let speed: f32 = if keys_pressed.contains(&KeyCode::ShiftLeft) {SPRINT_SPEED} else {SPEED} as f32 * delta_time;
-        let camera_right: Vec3 = self.camera_front.cross(Vec3::Y).normalize();
-        let camera_horizontal_front: Vec3 = Vec3::new(self.camera_front.x, 0.0, self.camera_front.z).normalize();
+        self.is_sneaking = actions.contains(&Action::Sneak) && !self.flying;
+        self.in_water = collision::any_block(world, &self.get_aabb(), block::is_fluid);
+        self.head_in_water = world.get_global_block(self.get_head_pos().floor().as_ivec3()) == block::WATER;
 
-        let mut move_dir: Vec3 = Vec3::ZERO;
-        let mut new_pos: Vec3 = self.pos;
+        self.update_breath(delta_time);
+        self.update_burning(delta_time, collision::any_block(world, &self.get_aabb(), |id| id == block::LAVA));
+
+        if self.pos.y < VOID_DEATH_HEIGHT {
+            self.damage(self.health);
+        }
+
+        if self.flying {
+            self.fly(delta_time, actions, stick, world);
+            return;
+        }
+
+        let mut speed: f32 = if self.is_sneaking {SNEAK_SPEED} else if actions.contains(&Action::Sprint) {SPRINT_SPEED} else {SPEED} * delta_time;
+
+        if self.in_water {
+            speed *= SWIM_SPEED_FACTOR;
+
+            self.vertical_velocity -= (GRAVITY as f32 - BUOYANCY) * delta_time;
+            self.vertical_velocity *= 1.0 - (WATER_DRAG * delta_time).min(1.0);
+            self.vertical_velocity = self.vertical_velocity.max(-MAX_SINK_SPEED);
+
+            if actions.contains(&Action::Jump) {
+                self.vertical_velocity = SWIM_UP_SPEED;
+            }
+        } else {
+            if actions.contains(&Action::Jump) && self.was_grounded {
+                self.vertical_velocity = JUMP_STRENGTH as f32;
+            }
+
+            self.vertical_velocity -= GRAVITY as f32 * delta_time;
+        }
+
+        let mut move_dir = self.get_move_dir(actions, stick, speed);
+
+        if self.is_sneaking && self.was_grounded {
+            move_dir = self.clamp_to_ledge(move_dir, world);
+        }
+
+        let motion: Vec3 = Vec3::new(move_dir.x, self.vertical_velocity * delta_time, move_dir.z);
+        let (mut moved, mut collided) = collision::move_and_collide(world, self.get_aabb(), motion);
+        let mut is_grounded: bool = collided.y && motion.y < 0.0;
 
-        if !self.was_grounded && is_grounded {
+        if self.was_grounded && (collided.x || collided.z) && motion.y <= 0.0 && let Some((step_moved, step_collided)) = self.try_step_up(move_dir, world) && step_moved.x.hypot(step_moved.z) > moved.x.hypot(moved.z) {
+            moved = step_moved;
+            collided = step_collided;
+            is_grounded = true;
+        }
+
+        self.pos += moved;
+
+        if !self.was_grounded && is_grounded && !self.in_water {
             let damage: f32 = (-self.vertical_velocity * 0.4) - 5.0;
 
             if damage > 0.0 {
@@ -58,82 +183,169 @@ impl Player {
 
         self.was_grounded = is_grounded;
 
-        if !is_grounded {
-            self.vertical_velocity -= GRAVITY as f32 * delta_time;
-        } else {
+        if collided.y {
             self.vertical_velocity = 0.0;
         }
+    }
 
-        if keys_pressed.contains(&KeyCode::Space) && is_grounded {
-            self.vertical_velocity += JUMP_STRENGTH as f32;
-        }
+    /// Horizontal movement of the held move actions, `speed` long in each direction, plus the stick scaled by how far it's pushed
+    fn get_move_dir(&self, actions: &HashSet<Action>, stick: Vec2, speed: f32) -> Vec3 {
+        let camera_right: Vec3 = self.camera_front.cross(Vec3::Y).normalize();
+        let camera_horizontal_front: Vec3 = Vec3::new(self.camera_front.x, 0.0, self.camera_front.z).normalize();
 
-        if keys_pressed.contains(&KeyCode::KeyW){
+        let mut move_dir: Vec3 = Vec3::ZERO;
+
+        if actions.contains(&Action::MoveForward){
             move_dir += speed * camera_horizontal_front;
         }
-        if keys_pressed.contains(&KeyCode::KeyS){
+        if actions.contains(&Action::MoveBackward){
             move_dir -= speed * camera_horizontal_front;
         }
-        if keys_pressed.contains(&KeyCode::KeyA){
+        if actions.contains(&Action::MoveLeft){
             move_dir -= speed * camera_right;
         }
-        if keys_pressed.contains(&KeyCode::KeyD){
+        if actions.contains(&Action::MoveRight){
             move_dir += speed * camera_right;
         }
 
-        if move_dir.x != 0.0 {
+        move_dir += speed * (stick.y * camera_horizontal_front + stick.x * camera_right);
 
-            let mut offset: f32 = 0.5;
+        move_dir
+    }
 
-            if move_dir.x < 0.0 {
-                offset = -0.5;
+    /// Spectators always fly, in creative a double tap of Jump toggles it
+    fn update_flight_toggle(&mut self, delta_time: f32, jump: bool) {
+        let pressed = jump && !self.jump_held;
+        self.jump_held = jump;
+        self.time_since_jump_press += delta_time;
+
+        match self.game_mode {
+            GameMode::Survival => self.flying = false,
+            GameMode::Spectator => self.flying = true,
+            GameMode::Creative if pressed => {
+                if self.time_since_jump_press < DOUBLE_TAP_TIME {
+                    self.flying = !self.flying;
+                    self.time_since_jump_press = DOUBLE_TAP_TIME; // A third tap starts a new double tap
+                } else {
+                    self.time_since_jump_press = 0.0;
+                }
             }
+            GameMode::Creative => {}
+        }
+    }
 
-            let block_foot: IVec3 = IVec3::new((self.pos.x + offset).floor() as i32, (self.pos.y + 0.5).floor() as i32, self.pos.z.floor() as i32);
-            let block_head: IVec3 = IVec3::new((self.pos.x + offset).floor() as i32, (self.pos.y + 1.5).floor() as i32, self.pos.z.floor() as i32);
+    /// Moves without gravity, Jump rises and Sneak sinks. Spectators pass through blocks, in creative touching the ground ends the flight.
+    fn fly(&mut self, delta_time: f32, actions: &HashSet<Action>, stick: Vec2, world: &World) {
+        let speed = if actions.contains(&Action::Sprint) {FLY_SPRINT_SPEED} else {FLY_SPEED} * delta_time;
+        let mut motion = self.get_move_dir(actions, stick, speed);
 
-            if self.is_block_at(block_head, &world) || self.is_block_at(block_foot, &world) {
-                move_dir.x = 0.0;
-            }
+        if actions.contains(&Action::Jump) {
+            motion.y += FLY_VERTICAL_SPEED * delta_time;
+        }
+        if actions.contains(&Action::Sneak) {
+            motion.y -= FLY_VERTICAL_SPEED * delta_time;
         }
 
-        if self.vertical_velocity > 0.0 {
-            let block_head: IVec3 = IVec3::new(self.pos.x.floor() as i32, (self.pos.y + (PLAYER_HEIGHT + 0.15)).floor() as i32, self.pos.z.floor() as i32);
+        self.vertical_velocity = 0.0;
 
-            if self.is_block_at(block_head, &world) {
-                self.vertical_velocity = 0.0;
-            }
+        if self.game_mode == GameMode::Spectator {
+            self.pos += motion;
+            self.was_grounded = false;
+            return;
         }
 
-        if move_dir.z != 0.0 {
+        let (moved, collided) = collision::move_and_collide(world, self.get_aabb(), motion);
+        self.pos += moved;
+        self.was_grounded = collided.y && motion.y < 0.0;
 
-            let mut offset: f32 = 0.5;
+        if self.was_grounded {
+            self.flying = false;
+        }
+    }
 
-            if move_dir.z < 0.0 {
-                offset = -0.5;
-            }
+    fn update_breath(&mut self, delta_time: f32) {
+        if !self.head_in_water {
+            self.breath = (self.breath + BREATH_REFILL_SPEED * delta_time).min(MAX_BREATH);
+            self.drown_timer = 0.0;
+            return;
+        }
+
+        self.breath = (self.breath - delta_time).max(0.0);
 
-            let block_foot: IVec3 = IVec3::new(self.pos.x.floor() as i32, (self.pos.y + 0.5).floor() as i32, (self.pos.z + offset).floor() as i32);
-            let block_head: IVec3 = IVec3::new(self.pos.x.floor() as i32, (self.pos.y + 1.5).floor() as i32, (self.pos.z + offset).floor() as i32);
+        if self.breath <= 0.0 {
+            self.drown_timer += delta_time;
 
-            if self.is_block_at(block_head, &world) || self.is_block_at(block_foot, &world) {
-                move_dir.z = 0.0;
+            if self.drown_timer >= DROWN_DAMAGE_INTERVAL {
+                self.drown_timer -= DROWN_DAMAGE_INTERVAL;
+                self.damage(1);
             }
         }
+    }
+
+    fn update_burning(&mut self, delta_time: f32, in_lava: bool) {
+        if !in_lava {
+            self.burn_timer = 0.0;
+            return;
+        }
 
-        new_pos += Vec3::new(0.0, self.vertical_velocity * delta_time, 0.0) + move_dir;
+        self.burn_timer += delta_time;
 
-        self.pos = new_pos;
+        if self.burn_timer >= LAVA_DAMAGE_INTERVAL {
+            self.burn_timer -= LAVA_DAMAGE_INTERVAL;
+            self.damage(1);
+        }
     }
 
-    fn is_block_at(&self, world_pos: IVec3, world: &World) -> bool {
-        world.get_global_block(world_pos) != 0
+    /// Moves up by at most the step height, then horizontally, then back down onto the obstacle
+    fn try_step_up(&self, move_dir: Vec3, world: &World) -> Option<(Vec3, BVec3)> {
+        let aabb = self.get_aabb();
+
+        let (up, _) = collision::move_and_collide(world, aabb, Vec3::new(0.0, self.step_height, 0.0));
+        if up.y <= 0.0 { return None; }
+
+        let (horizontal, horizontal_collided) = collision::move_and_collide(world, aabb.offset(up), Vec3::new(move_dir.x, 0.0, move_dir.z));
+        let (down, down_collided) = collision::move_and_collide(world, aabb.offset(up + horizontal), Vec3::new(0.0, -up.y, 0.0));
+
+        if !down_collided.y { return None; } // Nothing to stand on after the step
+
+        Some((up + horizontal + down, BVec3::new(horizontal_collided.x, true, horizontal_collided.z)))
     }
 
-    pub fn update_rotation(&mut self, delta: (f64, f64)) {
-        self.yaw += delta.0 as f32 * MOUSE_SENS;
-        self.pitch -= delta.1 as f32 * MOUSE_SENS;
-        self.pitch = self.pitch.clamp(-89.0, 89.0);
+    /// Shortens the horizontal movement so the player doesn't walk off a ledge
+    fn clamp_to_ledge(&self, mut move_dir: Vec3, world: &World) -> Vec3 {
+        let aabb = self.get_aabb();
+        let is_supported = |offset: Vec3| collision::collides(world, &aabb.offset(offset + Vec3::new(0.0, -LEDGE_DEPTH, 0.0)));
+
+        while move_dir.x != 0.0 && !is_supported(Vec3::new(move_dir.x, 0.0, 0.0)) {
+            move_dir.x = if move_dir.x.abs() < LEDGE_STEP {0.0} else {move_dir.x - LEDGE_STEP * move_dir.x.signum()};
+        }
+
+        while move_dir.z != 0.0 && !is_supported(Vec3::new(0.0, 0.0, move_dir.z)) {
+            move_dir.z = if move_dir.z.abs() < LEDGE_STEP {0.0} else {move_dir.z - LEDGE_STEP * move_dir.z.signum()};
+        }
+
+        while move_dir.x != 0.0 && move_dir.z != 0.0 && !is_supported(Vec3::new(move_dir.x, 0.0, move_dir.z)) {
+            move_dir.x = if move_dir.x.abs() < LEDGE_STEP {0.0} else {move_dir.x - LEDGE_STEP * move_dir.x.signum()};
+            move_dir.z = if move_dir.z.abs() < LEDGE_STEP {0.0} else {move_dir.z - LEDGE_STEP * move_dir.z.signum()};
+        }
+
+        move_dir
+    }
+
+    pub fn get_aabb(&self) -> Aabb {
+        Aabb::from_feet(self.pos, PLAYER_WIDTH, PLAYER_HEIGHT)
+    }
+
+    pub fn update_rotation(&mut self, delta: (f64, f64), sensitivity: f32) {
+        self.set_rotation(self.yaw + delta.0 as f32 * MOUSE_SENS * sensitivity, self.pitch - delta.1 as f32 * MOUSE_SENS * sensitivity);
+    }
+
+    /// Yaw and pitch in degrees
+    pub fn get_rotation(&self) -> (f32, f32) {(self.yaw, self.pitch)}
+
+    pub fn set_rotation(&mut self, yaw: f32, pitch: f32) {
+        self.yaw = yaw;
+        self.pitch = pitch.clamp(-89.0, 89.0);
 
         let yaw_rad = self.yaw.to_radians();
         let pitch_rad = self.pitch.to_radians();
@@ -141,19 +353,348 @@ impl Player {
         self.camera_front = Vec3::new(yaw_rad.cos() * pitch_rad.cos(), pitch_rad.sin(), yaw_rad.sin() * pitch_rad.cos()).normalize();
     }
 
-    pub fn get_pos(&self) -> Vec3 {self.pos}
+    /// Moves the player without interpolating or keeping the fall speed, like a teleport
+    pub fn set_pos(&mut self, pos: Vec3) {
+        self.pos = pos;
+        self.prev_pos = pos;
+        self.vertical_velocity = 0.0;
+    }
 
-    pub fn get_head_pos(&self) -> Vec3 {Vec3::new(self.pos.x, self.pos.y + PLAYER_HEIGHT, self.pos.z)}
+    pub fn get_head_pos(&self) -> Vec3 {Vec3::new(self.pos.x, self.pos.y + if self.is_sneaking {SNEAK_EYE_HEIGHT} else {PLAYER_HEIGHT}, self.pos.z)}
+
+    /// Head position between the last two ticks, `alpha` is the fraction of a tick passed since the last one
+    pub fn get_interpolated_head_pos(&self, alpha: f32) -> Vec3 {self.get_head_pos() - (self.pos - self.prev_pos) * (1.0 - alpha)}
 
     pub fn get_camera_front(&self) -> Vec3 {self.camera_front}
     
+    pub fn get_step_height(&self) -> f32 {self.step_height}
+
+    pub fn set_step_height(&mut self, step_height: f32) {self.step_height = step_height}
+
     pub fn get_health(&self) -> u8 {self.health}
 
+    pub fn set_health(&mut self, health: u8) {self.health = health}
+
+    pub fn get_pos(&self) -> Vec3 {self.pos}
+
+    pub fn is_underwater(&self) -> bool {self.head_in_water}
+
+    /// Remaining breath between 0 and 1
+    pub fn get_breath(&self) -> f32 {self.breath / MAX_BREATH}
+
+    pub fn get_inventory(&self) -> &Inventory {&self.inventory}
+
+    pub fn get_inventory_mut(&mut self) -> &mut Inventory {&mut self.inventory}
+
+    pub fn get_game_mode(&self) -> GameMode {self.game_mode}
+
+    pub fn set_game_mode(&mut self, game_mode: GameMode) {self.game_mode = game_mode}
+
+    pub fn is_flying(&self) -> bool {self.flying}
+
+    pub fn get_spawn_point(&self) -> Option<IVec3> {self.spawn_point}
+
+    pub fn set_spawn_point(&mut self, spawn_point: Option<IVec3>) {self.spawn_point = spawn_point}
+
+    /// Only hurts in survival
     pub fn damage(&mut self, damage: u8) {
-        if (self.health as i8 - damage as i8) <= 0 {
-            self.health = 0;
-        } else {
-            self.health -= damage
+        if self.game_mode != GameMode::Survival {
+            return;
         }
+
+        self.health = self.health.saturating_sub(damage);
+    }
+
+    pub fn is_dead(&self) -> bool {self.health == 0}
+
+    /// Brings the player back to life at `pos` with full health and breath, the hotbar is kept
+    pub fn respawn(&mut self, pos: Vec3) {
+        self.set_pos(pos);
+        self.health = MAX_HEALTH;
+        self.breath = MAX_BREATH;
+        self.drown_timer = 0.0;
+        self.burn_timer = 0.0;
+        self.was_grounded = false;
+        self.flying = false;
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gamestate::TICK_TIME;
+    use crate::world::fluid::SOURCE_LEVEL;
+    use crate::world::test_util::{empty_world, world_with_floor};
+    use glam::IVec3;
+
+    fn player_at(pos: Vec3) -> Player {
+        let mut player = Player::new();
+        player.pos = pos;
+        player
+    }
+
+    fn simulate(player: &mut Player, world: &World, actions: &[Action], ticks: u32) {
+        let actions: HashSet<Action> = actions.iter().copied().collect();
+
+        for _ in 0..ticks {
+            player.update_pos(TICK_TIME, &actions, Vec2::ZERO, world);
+        }
+    }
+
+    #[test]
+    fn lands_on_top_of_floor() {
+        let world = world_with_floor(9, block::STONE);
+        let mut player = player_at(Vec3::new(8.5, 14.0, 8.5));
+
+        simulate(&mut player, &world, &[], 40);
+
+        assert!((player.pos.y - 10.0).abs() < 0.01, "player stopped at y = {}", player.pos.y);
+        assert!(player.was_grounded);
+    }
+
+    #[test]
+    fn stands_on_block_edge() {
+        let mut world = empty_world();
+        world.update_block(IVec3::new(8, 9, 8), block::STONE, 0);
+        let mut player = player_at(Vec3::new(9.2, 10.0, 8.5)); // Only 0.1 of the player overlaps the block
+
+        simulate(&mut player, &world, &[], 20);
+
+        assert!((player.pos.y - 10.0).abs() < 0.01, "player fell to y = {}", player.pos.y);
+    }
+
+    #[test]
+    fn no_tunneling_at_high_fall_speed() {
+        let world = world_with_floor(9, block::STONE);
+        let mut player = player_at(Vec3::new(8.5, 15.0, 8.5));
+        player.vertical_velocity = -500.0;
+
+        simulate(&mut player, &world, &[], 1);
+
+        assert!((player.pos.y - 10.0).abs() < 0.01, "player ended at y = {}", player.pos.y);
+    }
+
+    #[test]
+    fn wall_blocks_movement() {
+        let mut world = world_with_floor(9, block::STONE);
+        for z in 0..16 {
+            for y in 10..12 {
+                world.update_block(IVec3::new(12, y, z), block::STONE, 0);
+            }
+        }
+
+        let mut player = player_at(Vec3::new(8.5, 10.0, 8.5));
+        player.update_rotation(((90.0 / MOUSE_SENS) as f64, 0.0), 1.0); // Face +X
+
+        simulate(&mut player, &world, &[Action::MoveForward], 40);
+
+        assert!(player.pos.x <= 12.0 - PLAYER_WIDTH / 2.0 + 0.001, "player walked into the wall at x = {}", player.pos.x);
+        assert!(player.pos.x > 11.0);
+    }
+
+    #[test]
+    fn half_pushed_stick_walks_at_half_speed() {
+        let world = world_with_floor(9, block::STONE);
+        let mut keyboard = player_at(Vec3::new(8.5, 10.0, 8.5));
+        let mut stick = player_at(Vec3::new(8.5, 10.0, 8.5));
+
+        simulate(&mut keyboard, &world, &[Action::MoveForward], 10);
+
+        for _ in 0..10 {
+            stick.update_pos(TICK_TIME, &HashSet::new(), Vec2::new(0.0, 0.5), &world);
+        }
+
+        let keyboard_distance = 8.5 - keyboard.pos.z;
+        let stick_distance = 8.5 - stick.pos.z;
+
+        assert!(keyboard_distance > 0.5);
+        assert!((stick_distance - keyboard_distance / 2.0).abs() < 0.001, "stick walked {stick_distance}, keyboard {keyboard_distance}");
+    }
+
+    #[test]
+    fn cannot_clip_through_block_corner() {
+        let mut world = world_with_floor(9, block::STONE);
+        world.update_block(IVec3::new(10, 10, 10), block::STONE, 0);
+        world.update_block(IVec3::new(10, 11, 10), block::STONE, 0);
+
+        let mut player = player_at(Vec3::new(9.5, 10.0, 9.5));
+        player.update_rotation(((135.0 / MOUSE_SENS) as f64, 0.0), 1.0); // Face diagonally towards +X +Z
+
+        simulate(&mut player, &world, &[Action::MoveForward], 10);
+
+        assert!(!player.get_aabb().intersects_block(IVec3::new(10, 10, 10)));
+    }
+
+    #[test]
+    fn steps_up_small_obstacles_but_not_full_blocks() {
+        let mut world = world_with_floor(9, block::STONE);
+        for x in 11..20 {
+            for z in 0..16 {
+                world.update_block(IVec3::new(x, 10, z), block::STONE, 0);
+            }
+        }
+
+        let walk_to_step = |step_height: f32| {
+            let mut player = player_at(Vec3::new(8.5, 10.0, 8.5));
+            player.set_step_height(step_height);
+            player.update_rotation(((90.0 / MOUSE_SENS) as f64, 0.0), 1.0); // Face +X
+
+            simulate(&mut player, &world, &[], 1);
+            simulate(&mut player, &world, &[Action::MoveForward], 20);
+            player
+        };
+
+        // A full block stops walking with the default step height
+        let player = walk_to_step(STEP_HEIGHT);
+        assert!(player.pos.x < 11.0, "player stepped up to x = {}", player.pos.x);
+        assert!((player.pos.y - 10.0).abs() < 0.01, "player is at y = {}", player.pos.y);
+
+        // A larger step height can be set per player
+        let player = walk_to_step(1.0);
+        assert!(player.pos.x > 11.5, "player got stuck at x = {}", player.pos.x);
+        assert!((player.pos.y - 11.0).abs() < 0.01, "player is at y = {}", player.pos.y);
+
+        // There are no slabs, so a half block step is made by standing half a block below the top of the obstacle
+        let mut player = player_at(Vec3::new(10.65, 10.5, 8.5));
+        player.update_rotation(((90.0 / MOUSE_SENS) as f64, 0.0), 1.0);
+        player.was_grounded = true;
+
+        simulate(&mut player, &world, &[Action::MoveForward], 1);
+
+        assert!(player.pos.x > 10.7, "player got stuck at x = {}", player.pos.x);
+        assert!((player.pos.y - 11.0).abs() < 0.01, "player is at y = {}", player.pos.y);
+    }
+
+    #[test]
+    fn sneaking_stops_at_ledge() {
+        let mut world = empty_world();
+        for x in 0..10 {
+            for z in 0..16 {
+                world.update_block(IVec3::new(x, 9, z), block::STONE, 0);
+            }
+        }
+
+        let mut player = player_at(Vec3::new(8.5, 10.0, 8.5));
+        player.update_rotation(((90.0 / MOUSE_SENS) as f64, 0.0), 1.0); // Face +X
+
+        simulate(&mut player, &world, &[], 1);
+        simulate(&mut player, &world, &[Action::MoveForward, Action::Sneak], 40);
+
+        assert!((player.pos.y - 10.0).abs() < 0.01, "player fell to y = {}", player.pos.y);
+        assert!(player.pos.x > 10.0 && player.pos.x < 10.0 + PLAYER_WIDTH / 2.0, "player stopped at x = {}", player.pos.x);
+    }
+
+    #[test]
+    fn sinks_slowly_and_swims_up_in_water() {
+        let mut world = world_with_floor(9, block::STONE);
+        for y in 10..30 {
+            for x in 6..11 {
+                for z in 6..11 {
+                    world.update_block(IVec3::new(x, y, z), block::WATER, SOURCE_LEVEL);
+                }
+            }
+        }
+
+        let mut player = player_at(Vec3::new(8.5, 20.0, 8.5));
+
+        simulate(&mut player, &world, &[], 20);
+        assert!(player.pos.y > 20.0 - MAX_SINK_SPEED - 0.01, "player sank to y = {}", player.pos.y);
+
+        let sunk_y = player.pos.y;
+        simulate(&mut player, &world, &[Action::Jump], 10);
+        assert!(player.pos.y > sunk_y, "player did not swim up from y = {}", sunk_y);
+    }
+
+    #[test]
+    fn drowns_when_out_of_breath() {
+        let mut world = world_with_floor(9, block::STONE);
+        for y in 10..14 {
+            world.update_block(IVec3::new(8, y, 8), block::WATER, SOURCE_LEVEL);
+        }
+
+        let mut player = player_at(Vec3::new(8.5, 10.0, 8.5));
+
+        simulate(&mut player, &world, &[], (MAX_BREATH / TICK_TIME) as u32);
+        assert_eq!(player.get_health(), 8);
+
+        simulate(&mut player, &world, &[], 50);
+        assert!(player.get_health() < 8);
+        assert_eq!(player.get_breath(), 0.0);
+    }
+
+    #[test]
+    fn ceiling_stops_jump() {
+        let mut world = world_with_floor(9, block::STONE);
+        world.update_block(IVec3::new(8, 12, 8), block::STONE, 0);
+
+        let mut player = player_at(Vec3::new(8.5, 10.0, 8.5));
+
+        simulate(&mut player, &world, &[], 1);
+        simulate(&mut player, &world, &[Action::Jump], 10);
+
+        assert!(player.get_aabb().max.y <= 12.001, "player head went into the ceiling at y = {}", player.get_aabb().max.y);
+    }
+
+    #[test]
+    fn double_tap_jump_flies_only_in_creative() {
+        let world = world_with_floor(9, block::STONE);
+        let mut player = player_at(Vec3::new(8.5, 10.0, 8.5));
+
+        let double_tap = |player: &mut Player| {
+            simulate(player, &world, &[Action::Jump], 1);
+            simulate(player, &world, &[], 2);
+            simulate(player, &world, &[Action::Jump], 1);
+        };
+
+        double_tap(&mut player);
+        assert!(!player.is_flying());
+
+        simulate(&mut player, &world, &[], 20);
+        player.set_game_mode(GameMode::Creative);
+        double_tap(&mut player);
+        assert!(player.is_flying());
+
+        simulate(&mut player, &world, &[Action::Jump], 15);
+        let height = player.pos.y;
+        simulate(&mut player, &world, &[], 15);
+        assert_eq!(player.pos.y, height, "player fell while flying");
+
+        // Landing ends the flight
+        simulate(&mut player, &world, &[Action::Sneak], 70);
+        assert!(!player.is_flying());
+        assert!((player.pos.y - 10.0).abs() < 0.01);
+
+        // Creative players don't take fall damage
+        player.set_pos(Vec3::new(8.5, 60.0, 8.5));
+        simulate(&mut player, &world, &[], 70);
+        assert_eq!(player.get_health(), 8);
+    }
+
+    #[test]
+    fn spectator_flies_through_blocks() {
+        let world = world_with_floor(9, block::STONE);
+        let mut player = player_at(Vec3::new(8.5, 12.0, 8.5));
+        player.set_game_mode(GameMode::Spectator);
+
+        simulate(&mut player, &world, &[Action::Sneak], 20);
+
+        assert!(player.is_flying());
+        assert!(player.pos.y < 9.0, "spectator stopped at y = {}", player.pos.y);
+    }
+
+    #[test]
+    fn interpolates_camera_between_ticks() {
+        let world = empty_world();
+        let mut player = player_at(Vec3::new(0.5, 150.0, 0.5));
+
+        simulate(&mut player, &world, &[], 10);
+
+        let previous = player.prev_pos.y + PLAYER_HEIGHT;
+        let current = player.get_head_pos().y;
+
+        assert_eq!(player.get_interpolated_head_pos(0.0).y, previous);
+        assert_eq!(player.get_interpolated_head_pos(1.0).y, current);
+        assert!((player.get_interpolated_head_pos(0.5).y - (previous + current) / 2.0).abs() < 0.0001);
     }
 }
@@ -0,0 +1,202 @@
+// SPDX-FileCopyrightText: © 2025 - 2026 Elias Steininger <elias.st4600@gmail.com> and Project Contributors (see CONTRIBUTORS.md)
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use crate::world::block;
+use crate::world::world::World;
+use glam::IVec3;
+use rand::{Rng, RngExt};
+use std::collections::{HashSet, VecDeque};
+
+pub const RANDOM_TICK_SPEED: u32 = 3; // Random cells picked per chunk section each tick
+pub const SECTION_HEIGHT: i32 = 16;
+
+const MIN_GROWTH_LIGHT: u8 = 9; // Light needed for grass to spread and saplings to grow
+const SAPLING_GROWTH_CHANCE: u32 = 7; // One in this many random ticks grows the tree
+pub const MIN_TRUNK_HEIGHT: i32 = 4;
+pub const MAX_TRUNK_HEIGHT: i32 = 6;
+const LEAF_DECAY_DISTANCE: i32 = 4; // Leaves further than this (through other leaves) from a log decay
+
+const NEIGHBORS: [IVec3; 6] = [IVec3::X, IVec3::NEG_X, IVec3::Y, IVec3::NEG_Y, IVec3::Z, IVec3::NEG_Z];
+
+/// Runs the random tick behaviour of the block at `pos`, if it has one
+pub fn random_tick(world: &mut World, pos: IVec3) {
+    match world.get_global_block(pos) {
+        block::GRASS => tick_grass(world, pos),
+        block::SAPLING => tick_sapling(world, pos),
+        block::LEAVES => tick_leaves(world, pos),
+        _ => {}
+    }
+}
+
+fn tick_grass(world: &mut World, pos: IVec3) {
+    let above = world.get_global_block(pos + IVec3::Y);
+
+    if block::is_opaque(above) || block::is_fluid(above) {
+        world.update_block(pos, block::DIRT, 0);
+        return;
+    }
+
+    if world.get_light(pos + IVec3::Y) < MIN_GROWTH_LIGHT {
+        return;
+    }
+
+    let rng = world.get_rng();
+    let target = pos + IVec3::new(rng.random_range(-1..=1), rng.random_range(-3..=1), rng.random_range(-1..=1));
+    let target_above = world.get_global_block(target + IVec3::Y);
+
+    if world.get_global_block(target) == block::DIRT && !block::is_opaque(target_above) && !block::is_fluid(target_above) && world.get_light(target + IVec3::Y) >= MIN_GROWTH_LIGHT {
+        world.update_block(target, block::GRASS, 0);
+    }
+}
+
+fn tick_sapling(world: &mut World, pos: IVec3) {
+    if world.get_light(pos) < MIN_GROWTH_LIGHT || world.get_rng().random_range(0..SAPLING_GROWTH_CHANCE) != 0 {
+        return;
+    }
+
+    let trunk_height = world.get_rng().random_range(MIN_TRUNK_HEIGHT..=MAX_TRUNK_HEIGHT);
+
+    if (1..=trunk_height).any(|y| world.get_global_block(pos + IVec3::Y * y) != block::AIR) {
+        return;
+    }
+
+    grow_tree(world, pos, trunk_height);
+}
+
+/// Places a trunk of logs with a rounded crown of leaves on top, leaves only replace air
+pub fn grow_tree(world: &mut World, base: IVec3, trunk_height: i32) {
+    for (offset, id) in get_tree_blocks(trunk_height, world.get_rng()) {
+        if id == block::LOG || world.get_global_block(base + offset) == block::AIR {
+            world.update_block(base + offset, id, 0);
+        }
+    }
+}
+
+/// Blocks of a tree relative to the bottom of its trunk
+pub fn get_tree_blocks(trunk_height: i32, rng: &mut impl Rng) -> Vec<(IVec3, u8)> {
+    let mut blocks: Vec<(IVec3, u8)> = (0..trunk_height).map(|y| (IVec3::Y * y, block::LOG)).collect();
+
+    for y in trunk_height - 2..=trunk_height + 1 {
+        let radius: i32 = if y < trunk_height {2} else {1};
+
+        for x in -radius..=radius {
+            for z in -radius..=radius {
+                let is_corner = x.abs() == radius && z.abs() == radius;
+
+                // Cut the corners of the wide layers randomly and always on the top layer
+                if is_corner && (y == trunk_height + 1 || (radius == 2 && rng.random_bool(0.5))) {
+                    continue;
+                }
+
+                if x != 0 || z != 0 || y >= trunk_height {
+                    blocks.push((IVec3::new(x, y, z), block::LEAVES));
+                }
+            }
+        }
+    }
+
+    blocks
+}
+
+fn tick_leaves(world: &mut World, pos: IVec3) {
+    if !is_connected_to_log(world, pos) {
+        world.update_block(pos, block::AIR, 0);
+    }
+}
+
+/// Breadth first search through leaves for a log within `LEAF_DECAY_DISTANCE` steps
+fn is_connected_to_log(world: &World, start: IVec3) -> bool {
+    let mut queue: VecDeque<(IVec3, i32)> = VecDeque::from([(start, 0)]);
+    let mut visited: HashSet<IVec3> = HashSet::from([start]);
+
+    while let Some((pos, distance)) = queue.pop_front() {
+        if distance >= LEAF_DECAY_DISTANCE {
+            continue;
+        }
+
+        for offset in NEIGHBORS {
+            let neighbor = pos + offset;
+
+            match world.get_global_block(neighbor) {
+                block::LOG => return true,
+                block::LEAVES if visited.insert(neighbor) => queue.push_back((neighbor, distance + 1)),
+                _ => {}
+            }
+        }
+    }
+
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::test_util::world_with_floor;
+
+    const GROUND_Y: i32 = 10;
+
+    #[test]
+    fn grass_turns_to_dirt_under_solid_block() {
+        let mut world = world_with_floor(GROUND_Y, block::GRASS);
+        let grass = IVec3::new(3, GROUND_Y, 3);
+
+        world.update_block(grass + IVec3::Y, block::STONE, 0);
+        random_tick(&mut world, grass);
+
+        assert_eq!(world.get_global_block(grass), block::DIRT);
+    }
+
+    #[test]
+    fn grass_spreads_onto_lit_dirt() {
+        let mut world = world_with_floor(GROUND_Y, block::DIRT);
+        let grass = IVec3::new(3, GROUND_Y, 3);
+
+        world.update_block(grass, block::GRASS, 0);
+
+        for _ in 0..500 {
+            random_tick(&mut world, grass);
+        }
+
+        let spread = (-1..=1).flat_map(|x| (-1..=1).map(move |z| IVec3::new(x, 0, z)))
+            .filter(|offset| world.get_global_block(grass + *offset) == block::GRASS)
+            .count();
+
+        assert_eq!(spread, 9);
+    }
+
+    #[test]
+    fn sapling_grows_into_tree() {
+        let mut world = world_with_floor(GROUND_Y, block::GRASS);
+        let sapling = IVec3::new(3, GROUND_Y + 1, 3);
+
+        world.update_block(sapling, block::SAPLING, 0);
+
+        for _ in 0..500 {
+            random_tick(&mut world, sapling);
+        }
+
+        let trunk_height = (0..=MAX_TRUNK_HEIGHT).take_while(|y| world.get_global_block(sapling + IVec3::Y * *y) == block::LOG).count() as i32;
+
+        assert!((MIN_TRUNK_HEIGHT..=MAX_TRUNK_HEIGHT).contains(&trunk_height));
+        assert_eq!(world.get_global_block(sapling + IVec3::Y * trunk_height), block::LEAVES);
+    }
+
+    #[test]
+    fn leaves_decay_without_log() {
+        let mut world = world_with_floor(GROUND_Y, block::GRASS);
+        let base = IVec3::new(3, GROUND_Y + 1, 3);
+
+        grow_tree(&mut world, base, MIN_TRUNK_HEIGHT);
+
+        let leaf = base + IVec3::new(1, MIN_TRUNK_HEIGHT - 1, 0);
+        random_tick(&mut world, leaf);
+        assert_eq!(world.get_global_block(leaf), block::LEAVES);
+
+        for y in 0..MIN_TRUNK_HEIGHT {
+            world.update_block(base + IVec3::Y * y, block::AIR, 0);
+        }
+
+        random_tick(&mut world, leaf);
+        assert_eq!(world.get_global_block(leaf), block::AIR);
+    }
+}
@@ -0,0 +1,579 @@
+// SPDX-FileCopyrightText: © 2025 - 2026 Elias Steininger <elias.st4600@gmail.com> and Project Contributors (see CONTRIBUTORS.md)
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use crate::world::block;
+use crate::world::chunk::Chunk;
+use crate::world::generator::{GeneratorPreset, GeneratorSettings, WorldGenerator};
+use crate::world::item::{Item, ItemStack, Tool, ToolKind, ToolTier, HOTBAR_SIZE, MAX_STACK_SIZE};
+use crate::world::player::{GameMode, Player};
+use crate::world::world::World;
+use glam::{IVec2, IVec3, Vec3};
+use glow::Program;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const INFO_FILE: &str = "world.txt";
+const PLAYER_FILE: &str = "player.txt";
+const CHUNK_DIR: &str = "chunks";
+const HEIGHTMAP_FILE: &str = "heightmap.png"; // Copy of the image a heightmap world was created from
+const PENDING_WRITES_FILE: &str = "decoration.txt"; // Decoration generated into chunks that weren't loaded yet
+
+/// Everything needed to continue generating a saved world
+pub struct WorldInfo {
+    pub name: String,
+    pub seed: u64,
+    pub preset: GeneratorPreset,
+    pub generator_settings: GeneratorSettings,
+    pub last_played: u64, // Seconds since the unix epoch
+    pub spawn: Option<IVec3> // None until the world is first loaded and a safe spawn is found
+}
+
+impl WorldInfo {
+    pub fn new(name: &str, seed: u64, preset: GeneratorPreset, generator_settings: GeneratorSettings) -> Self {
+        WorldInfo {
+            name: name.to_string(),
+            seed,
+            preset,
+            generator_settings,
+            last_played: get_time(),
+            spawn: None
+        }
+    }
+
+    fn to_text(&self) -> String {
+        let text = format!(
+            "name = {}\nseed = {}\ngenerator = {}\nsuperflat_layers = {}\nheightmap_path = {}\nheightmap_materials = {}\nlast_played = {}\n",
+            self.name, self.seed, self.preset.get_name(), self.generator_settings.superflat_layers, self.generator_settings.heightmap_path, self.generator_settings.heightmap_materials, self.last_played
+        );
+
+        match self.spawn {
+            Some(spawn) => text + &format!("spawn = {}\n", block_pos_to_text(spawn)),
+            None => text
+        }
+    }
+
+    fn from_text(text: &str) -> Result<Self, String> {
+        let values = parse_key_values(text);
+        let get = |key: &str| values.get(key).cloned().ok_or(format!("Missing '{key}'"));
+
+        Ok(WorldInfo {
+            name: get("name")?,
+            seed: get("seed")?.parse().map_err(|_| "Invalid seed")?,
+            preset: GeneratorPreset::from_name(&get("generator")?).ok_or("Unknown generator")?,
+            generator_settings: GeneratorSettings {
+                superflat_layers: get("superflat_layers")?,
+                heightmap_path: get("heightmap_path")?,
+                heightmap_materials: get("heightmap_materials")? == "true"
+            },
+            last_played: get("last_played")?.parse().map_err(|_| "Invalid last_played")?,
+            spawn: values.get("spawn").and_then(|spawn| parse_block_pos(spawn))
+        })
+    }
+}
+
+/// Reads `key = value` lines, lines starting with `#` are comments
+pub fn parse_key_values(text: &str) -> HashMap<String, String> {
+    text.lines()
+        .filter(|line| !line.trim_start().starts_with('#'))
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+        .collect()
+}
+
+pub fn get_time() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |time| time.as_secs())
+}
+
+/// Directory all worlds are saved in, inside the user's data directory
+pub fn get_saves_dir() -> PathBuf {
+    dirs_next::data_dir().unwrap_or_default().join("Brickbyte").join("saves")
+}
+
+/// Every world in `saves_dir` with its info, the most recently played first
+pub fn list_worlds(saves_dir: &Path) -> Vec<(PathBuf, WorldInfo)> {
+    let Ok(entries) = fs::read_dir(saves_dir) else {
+        return Vec::new();
+    };
+
+    let mut worlds: Vec<(PathBuf, WorldInfo)> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| Some((entry.path(), load_info(&entry.path()).ok()?)))
+        .collect();
+
+    worlds.sort_by_key(|(_, info)| std::cmp::Reverse(info.last_played));
+    worlds
+}
+
+/// Unused directory for a new world, named after it without characters that are invalid in file names
+pub fn get_new_world_dir(saves_dir: &Path, name: &str) -> PathBuf {
+    let dir_name: String = name.trim().chars().map(|c| if c.is_alphanumeric() || c == ' ' || c == '-' {c} else {'_'}).collect();
+    let dir_name = if dir_name.is_empty() {"World".to_string()} else {dir_name};
+
+    (1..).map(|i| if i == 1 {saves_dir.join(&dir_name)} else {saves_dir.join(format!("{dir_name} {i}"))}).find(|dir| !dir.exists()).unwrap()
+}
+
+/// Changes the displayed name, the directory keeps its name
+pub fn rename_world(dir: &Path, name: &str) -> Result<(), String> {
+    let mut info = load_info(dir)?;
+    info.name = name.to_string();
+
+    write(&dir.join(INFO_FILE), info.to_text().as_bytes())
+}
+
+/// Copies a world next to the original and returns the directory of the copy
+pub fn duplicate_world(dir: &Path, saves_dir: &Path) -> Result<PathBuf, String> {
+    let name = format!("{} (Copy)", load_info(dir)?.name);
+    let copy = get_new_world_dir(saves_dir, &name);
+
+    copy_dir(dir, &copy)?;
+    rename_world(&copy, &name)?;
+
+    Ok(copy)
+}
+
+fn copy_dir(from: &Path, to: &Path) -> Result<(), String> {
+    fs::create_dir_all(to).map_err(|error| format!("Failed to create '{}': {error}", to.display()))?;
+
+    for entry in fs::read_dir(from).map_err(|error| format!("Failed to read '{}': {error}", from.display()))? {
+        let path = entry.map_err(|error| error.to_string())?.path();
+        let target = to.join(path.file_name().unwrap_or_default());
+
+        if path.is_dir() {
+            copy_dir(&path, &target)?;
+        } else {
+            fs::copy(&path, &target).map_err(|error| format!("Failed to copy '{}': {error}", path.display()))?;
+        }
+    }
+
+    Ok(())
+}
+
+pub fn delete_world(dir: &Path) -> Result<(), String> {
+    fs::remove_dir_all(dir).map_err(|error| format!("Failed to delete '{}': {error}", dir.display()))
+}
+
+/// Numbers are used as they are, any other text is hashed, so `Brickbyte` is a valid seed too
+pub fn parse_seed(text: &str) -> Option<u64> {
+    let text = text.trim();
+
+    if text.is_empty() {
+        return None;
+    }
+
+    // FNV-1a, which unlike the standard library's hasher is the same on every platform and version
+    Some(text.parse().unwrap_or_else(|_| text.bytes().fold(0xCBF2_9CE4_8422_2325, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x100_0000_01B3))))
+}
+
+/// Seconds since the unix epoch as `YYYY-MM-DD HH:MM` in UTC
+pub fn format_time(time: u64) -> String {
+    let days = (time / 86400) as i64;
+    let minutes = time % 86400 / 60;
+
+    // Converts days since the epoch to a date, from Howard Hinnant's `civil_from_days`
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let day_of_era = z.rem_euclid(146097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {month_index + 3} else {month_index - 9};
+    let year = year_of_era + era * 400 + if month <= 2 {1} else {0};
+
+    format!("{year:04}-{month:02}-{day:02} {:02}:{:02}", minutes / 60, minutes % 60)
+}
+
+/// Writes the world info, the player, the pending decoration and every loaded chunk into `dir`
+pub fn save_world(dir: &Path, info: &WorldInfo, world: &World, player: &Player) -> Result<(), String> {
+    let chunk_dir = dir.join(CHUNK_DIR);
+    fs::create_dir_all(&chunk_dir).map_err(|error| format!("Failed to create '{}': {error}", chunk_dir.display()))?;
+
+    write(&dir.join(INFO_FILE), info.to_text().as_bytes())?;
+    write(&dir.join(PLAYER_FILE), player_to_text(player).as_bytes())?;
+    write(&dir.join(PENDING_WRITES_FILE), pending_writes_to_text(world).as_bytes())?;
+
+    // Chunks that were never saved are generated from the copy, so the world keeps loading when the original image is moved
+    let heightmap = dir.join(HEIGHTMAP_FILE);
+
+    if info.preset == GeneratorPreset::Heightmap && !heightmap.exists() {
+        fs::copy(&info.generator_settings.heightmap_path, &heightmap).map_err(|error| format!("Failed to copy the heightmap: {error}"))?;
+    }
+
+    for chunk in world.get_chunks() {
+        write(&chunk_dir.join(get_chunk_file_name(chunk.position)), &chunk.get_data())?;
+    }
+
+    Ok(())
+}
+
+/// Writes a single chunk, for chunks that are unloaded while the world is played
+pub fn save_chunk(dir: &Path, chunk: &Chunk) -> Result<(), String> {
+    let chunk_dir = dir.join(CHUNK_DIR);
+    fs::create_dir_all(&chunk_dir).map_err(|error| format!("Failed to create '{}': {error}", chunk_dir.display()))?;
+
+    write(&chunk_dir.join(get_chunk_file_name(chunk.position)), &chunk.get_data())
+}
+
+fn write(path: &Path, data: &[u8]) -> Result<(), String> {
+    fs::write(path, data).map_err(|error| format!("Failed to write '{}': {error}", path.display()))
+}
+
+fn get_chunk_file_name(pos: IVec2) -> String {
+    format!("{}_{}.bin", pos.x, pos.y)
+}
+
+/// The generator of a saved world, heightmap worlds use the copy of their image inside the save if there is one
+pub fn create_generator(dir: &Path, info: &WorldInfo) -> Result<Box<dyn WorldGenerator>, String> {
+    let heightmap = dir.join(HEIGHTMAP_FILE);
+
+    if info.preset != GeneratorPreset::Heightmap || !heightmap.exists() {
+        return info.preset.create(info.seed, &info.generator_settings);
+    }
+
+    let settings = GeneratorSettings {
+        heightmap_path: heightmap.to_string_lossy().into_owned(),
+        ..info.generator_settings.clone()
+    };
+
+    info.preset.create(info.seed, &settings)
+}
+
+pub fn load_info(dir: &Path) -> Result<WorldInfo, String> {
+    let text = fs::read_to_string(dir.join(INFO_FILE)).map_err(|error| format!("Failed to read '{}': {error}", dir.display()))?;
+
+    WorldInfo::from_text(&text)
+}
+
+/// The saved chunk, or None if it was never saved and has to be generated
+pub fn load_chunk(dir: &Path, pos: IVec2, shader: Program) -> Option<Chunk> {
+    let data = fs::read(dir.join(CHUNK_DIR).join(get_chunk_file_name(pos))).ok()?;
+    let mut chunk = Chunk::new(pos, shader);
+
+    chunk.set_data(&data).then_some(chunk)
+}
+
+/// Restores the decoration waiting for chunks that aren't loaded, before any chunk of the world is added.
+/// Worlds saved before it was kept have no file and nothing is pending.
+pub fn load_pending_writes(dir: &Path, world: &mut World) {
+    let Ok(text) = fs::read_to_string(dir.join(PENDING_WRITES_FILE)) else {
+        return;
+    };
+
+    for line in text.lines() {
+        if let Some((pos, id)) = line.rsplit_once(' ') && let (Some(pos), Ok(id)) = (parse_block_pos(pos), id.parse()) {
+            world.add_pending_write(pos, id);
+        }
+    }
+}
+
+/// One `<x> <y> <z> <block>` line per write
+fn pending_writes_to_text(world: &World) -> String {
+    world.get_pending_writes().iter().map(|(pos, id)| format!("{} {id}\n", block_pos_to_text(*pos))).collect()
+}
+
+/// Restores position, rotation, health, game mode, spawn point and inventory.
+/// Returns false if there is no player file, the player keeps its defaults then.
+pub fn load_player(dir: &Path, player: &mut Player) -> Result<bool, String> {
+    let Ok(text) = fs::read_to_string(dir.join(PLAYER_FILE)) else {
+        return Ok(false);
+    };
+
+    let values = parse_key_values(&text);
+    let get_floats = |key: &str| -> Result<Vec<f32>, String> {
+        values.get(key).ok_or(format!("Missing '{key}'"))?.split_whitespace().map(|value| value.parse().map_err(|_| format!("Invalid '{key}'"))).collect()
+    };
+
+    let pos = get_floats("pos")?;
+    let rotation = get_floats("rotation")?;
+
+    if pos.len() != 3 || rotation.len() != 2 || !pos.iter().chain(&rotation).all(|value| value.is_finite()) {
+        return Err("Invalid player position".to_string());
+    }
+
+    player.set_pos(Vec3::new(pos[0], pos[1], pos[2]));
+    player.set_rotation(rotation[0], rotation[1]);
+    player.set_health(values.get("health").and_then(|health| health.parse().ok()).ok_or("Invalid health")?);
+    player.set_game_mode(values.get("game_mode").and_then(|name| GameMode::from_name(name)).unwrap_or(GameMode::Survival));
+    player.set_spawn_point(values.get("spawn_point").and_then(|spawn_point| parse_block_pos(spawn_point)));
+
+    if let Some(step_height) = values.get("step_height").and_then(|step_height| step_height.parse().ok()) {
+        player.set_step_height(step_height);
+    }
+
+    for i in 0..HOTBAR_SIZE {
+        player.get_inventory_mut().set_slot(i, values.get(&format!("slot_{i}")).and_then(|stack| parse_stack(stack)));
+    }
+
+    Ok(true)
+}
+
+fn player_to_text(player: &Player) -> String {
+    let pos = player.get_pos();
+    let (yaw, pitch) = player.get_rotation();
+    let mut text = format!("pos = {} {} {}\nrotation = {yaw} {pitch}\nhealth = {}\ngame_mode = {}\nstep_height = {}\n", pos.x, pos.y, pos.z, player.get_health(), player.get_game_mode().get_name(), player.get_step_height());
+
+    if let Some(spawn_point) = player.get_spawn_point() {
+        text += &format!("spawn_point = {}\n", block_pos_to_text(spawn_point));
+    }
+
+    for i in 0..HOTBAR_SIZE {
+        if let Some(stack) = player.get_inventory().get_slot(i) {
+            text += &format!("slot_{i} = {}\n", stack_to_text(stack));
+        }
+    }
+
+    text
+}
+
+fn block_pos_to_text(pos: IVec3) -> String {
+    format!("{} {} {}", pos.x, pos.y, pos.z)
+}
+
+fn parse_block_pos(text: &str) -> Option<IVec3> {
+    let coordinates: Vec<i32> = text.split_whitespace().map(|value| value.parse().ok()).collect::<Option<_>>()?;
+
+    match coordinates.as_slice() {
+        [x, y, z] => Some(IVec3::new(*x, *y, *z)),
+        _ => None
+    }
+}
+
+/// `block <id> <count>` or `tool <kind> <tier> <durability>`
+fn stack_to_text(stack: &ItemStack) -> String {
+    match stack.item {
+        Item::Block(id) => format!("block {id} {}", stack.count),
+        Item::Tool(tool) => format!("tool {} {} {}", tool.kind.get_name(), tool.tier.get_name(), tool.get_durability())
+    }
+}
+
+/// None for unknown items, air, empty or overfull stacks and broken tools, which the game can't hold
+fn parse_stack(text: &str) -> Option<ItemStack> {
+    let parts: Vec<&str> = text.split_whitespace().collect();
+
+    match parts.as_slice() {
+        ["block", id, count] => {
+            let id = id.parse().ok().filter(|id| (1..block::BLOCK_COUNT as u8).contains(id))?;
+            let count = count.parse().ok().filter(|count| (1..=MAX_STACK_SIZE).contains(count))?;
+
+            Some(ItemStack::new(Item::Block(id), count))
+        }
+        ["tool", kind, tier, durability] => {
+            let kind = *ToolKind::ALL.iter().find(|tool_kind| tool_kind.get_name() == *kind)?;
+            let tier = *ToolTier::ALL.iter().find(|tool_tier| tool_tier.get_name() == *tier)?;
+            let durability = durability.parse().ok().filter(|durability| *durability > 0)?;
+
+            Some(ItemStack::new(Item::Tool(Tool::with_durability(kind, tier, durability)), 1))
+        }
+        _ => None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::chunk::CHUNK_HEIGHT;
+    use crate::world::biome::Biome;
+    use crate::world::terrain::TerrainGenerator;
+    use crate::world::test_util::{get_dummy_shader, get_temp_path, world_with_floor};
+
+
+    #[test]
+    fn world_round_trips_through_save() {
+        let dir = get_temp_path("world");
+        let world = world_with_floor(10, block::BRICKS);
+        let mut info = WorldInfo::new("Test World", 42, GeneratorPreset::Superflat, GeneratorSettings::default());
+        info.spawn = Some(IVec3::new(3, 11, -2));
+
+        save_world(&dir, &info, &world, &Player::new()).unwrap();
+
+        let loaded = load_info(&dir).unwrap();
+        assert_eq!(loaded.name, "Test World");
+        assert_eq!(loaded.seed, 42);
+        assert_eq!(loaded.preset, GeneratorPreset::Superflat);
+        assert_eq!(loaded.generator_settings.superflat_layers, info.generator_settings.superflat_layers);
+        assert_eq!(loaded.spawn, Some(IVec3::new(3, 11, -2)));
+
+        let chunk = load_chunk(&dir, IVec2::ZERO, get_dummy_shader()).unwrap();
+        assert_eq!(chunk.get_block(IVec3::new(3, 10, 3)), block::BRICKS);
+        assert!(load_chunk(&dir, IVec2::new(50, 50), get_dummy_shader()).is_none());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn worlds_can_be_renamed_duplicated_and_deleted() {
+        let saves_dir = get_temp_path("manage");
+        let dir = get_new_world_dir(&saves_dir, "My/World");
+        let mut info = WorldInfo::new("My/World", 7, GeneratorPreset::Void, GeneratorSettings::default());
+
+        assert_eq!(dir, saves_dir.join("My_World"));
+
+        info.last_played = 100;
+        save_world(&dir, &info, &world_with_floor(5, block::STONE), &Player::new()).unwrap();
+        assert_eq!(get_new_world_dir(&saves_dir, "My/World"), saves_dir.join("My_World 2"));
+
+        rename_world(&dir, "Renamed").unwrap();
+        let copy = duplicate_world(&dir, &saves_dir).unwrap();
+
+        let worlds = list_worlds(&saves_dir);
+        assert_eq!(worlds.len(), 2);
+        assert_eq!(load_info(&copy).unwrap().name, "Renamed (Copy)");
+        assert!(load_chunk(&copy, IVec2::ZERO, get_dummy_shader()).is_some());
+
+        delete_world(&dir).unwrap();
+        let worlds = list_worlds(&saves_dir);
+        assert_eq!(worlds.len(), 1);
+        assert_eq!(worlds[0].0, copy);
+
+        let _ = fs::remove_dir_all(&saves_dir);
+    }
+
+    #[test]
+    fn seeds_and_times_are_readable() {
+        assert_eq!(parse_seed("12345"), Some(12345));
+        assert_eq!(parse_seed("Brickbyte"), parse_seed(" Brickbyte "));
+        assert_ne!(parse_seed("Brickbyte"), parse_seed("brickbyte"));
+        assert_eq!(parse_seed(""), None);
+
+        assert_eq!(format_time(0), "1970-01-01 00:00");
+        assert_eq!(format_time(1_709_210_096), "2024-02-29 12:34");
+    }
+
+    #[test]
+    fn player_round_trips_through_save() {
+        let dir = get_temp_path("player");
+        let mut player = Player::new();
+
+        player.set_pos(Vec3::new(1.5, 80.0, -3.25));
+        player.set_rotation(45.0, -20.0);
+        player.set_health(3);
+        player.set_game_mode(GameMode::Creative);
+        player.set_step_height(0.5);
+        player.set_spawn_point(Some(IVec3::new(-4, 70, 12)));
+        player.get_inventory_mut().set_slot(0, Some(ItemStack::new(Item::Tool(Tool::with_durability(ToolKind::Axe, ToolTier::Iron, 17)), 1)));
+        player.get_inventory_mut().clear_slot(3);
+
+        save_world(&dir, &WorldInfo::new("Player", 1, GeneratorPreset::Void, GeneratorSettings::default()), &World::new(), &player).unwrap();
+
+        let mut loaded = Player::new();
+        load_player(&dir, &mut loaded).unwrap();
+
+        assert_eq!(loaded.get_pos(), Vec3::new(1.5, 80.0, -3.25));
+        assert_eq!(loaded.get_rotation(), (45.0, -20.0));
+        assert_eq!(loaded.get_health(), 3);
+        assert_eq!(loaded.get_game_mode(), GameMode::Creative);
+        assert_eq!(loaded.get_step_height(), 0.5);
+        assert_eq!(loaded.get_spawn_point(), Some(IVec3::new(-4, 70, 12)));
+        assert_eq!(loaded.get_inventory().get_slot(0), player.get_inventory().get_slot(0));
+        assert_eq!(loaded.get_inventory().get_slot(3), None);
+        assert_eq!(loaded.get_inventory().get_slot(4), player.get_inventory().get_slot(4));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn invalid_stacks_are_dropped() {
+        for stack in [ItemStack::new(Item::Block(block::SAND), 1), ItemStack::new(Item::Block(block::BRICKS), MAX_STACK_SIZE), ItemStack::new(Item::Tool(Tool::with_durability(ToolKind::Shovel, ToolTier::Diamond, 1)), 1)] {
+            assert_eq!(parse_stack(&stack_to_text(&stack)), Some(stack));
+        }
+
+        for text in ["block 0 1", "block 200 1", &format!("block {} 1", block::BLOCK_COUNT), "block 3 0", "block 3 65", "block 3 -1", "tool pickaxe iron 0", "tool spoon iron 5", "block 3"] {
+            assert_eq!(parse_stack(text), None, "{text}");
+        }
+
+        // A hand edited save loads with the broken slots empty
+        let dir = get_temp_path("invalid_stacks");
+        save_world(&dir, &WorldInfo::new("Stacks", 1, GeneratorPreset::Void, GeneratorSettings::default()), &World::new(), &Player::new()).unwrap();
+
+        let text = fs::read_to_string(dir.join(PLAYER_FILE)).unwrap();
+        fs::write(dir.join(PLAYER_FILE), text + "slot_0 = block 0 1\nslot_1 = block 3 0\nslot_2 = block 200 1\n").unwrap();
+
+        let mut loaded = Player::new();
+        load_player(&dir, &mut loaded).unwrap();
+
+        for i in 0..3 {
+            assert_eq!(loaded.get_inventory().get_slot(i), None);
+        }
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn heightmap_worlds_load_without_the_original_image() {
+        let dir = get_temp_path("heightmap_world");
+        let image = get_temp_path("heightmap_world.png");
+        let mut encoder = png::Encoder::new(std::io::BufWriter::new(fs::File::create(&image).unwrap()), 1, 1);
+        encoder.set_color(png::ColorType::Grayscale);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder.write_header().unwrap().write_image_data(&[128]).unwrap();
+
+        let settings = GeneratorSettings {heightmap_path: image.to_string_lossy().into_owned(), ..GeneratorSettings::default()};
+        let info = WorldInfo::new("Heightmap", 1, GeneratorPreset::Heightmap, settings);
+        let spawn_height = info.preset.create(info.seed, &info.generator_settings).unwrap().get_spawn_height(0, 0);
+
+        save_world(&dir, &info, &World::new(), &Player::new()).unwrap();
+        fs::remove_file(&image).unwrap();
+
+        assert!(info.preset.create(info.seed, &info.generator_settings).is_err());
+        assert_eq!(create_generator(&dir, &load_info(&dir).unwrap()).unwrap().get_spawn_height(0, 0), spawn_height);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn decoration_reaches_chunks_that_were_saved_before_it() {
+        let dir = get_temp_path("decoration");
+        let shader = get_dummy_shader();
+        let generator = TerrainGenerator::new(12345678910);
+
+        // A forest, so trees grow over chunk borders
+        let origin = (-64..64).map(|x| IVec2::new(x, 0)).find(|pos| generator.get_biome(pos.x * 16, 0) == Biome::Forest).unwrap();
+        let positions: Vec<IVec2> = (0..9).map(|i| origin + IVec2::new(i % 3, i / 3)).collect();
+
+        let mut expected = World::new();
+
+        for pos in &positions {
+            expected.insert_chunk(*pos, shader, &generator);
+        }
+
+        // Every chunk is saved and unloaded before its neighbours generate, like while walking away from it
+        let mut streamed = World::new();
+
+        for pos in &positions {
+            streamed.insert_chunk(*pos, shader, &generator);
+
+            for chunk in streamed.remove_chunks_outside(IVec2::splat(1000), 0) {
+                save_chunk(&dir, &chunk).unwrap();
+            }
+        }
+
+        save_world(&dir, &WorldInfo::new("Forest", 1, GeneratorPreset::Default, GeneratorSettings::default()), &streamed, &Player::new()).unwrap();
+
+        let mut loaded = World::new();
+        load_pending_writes(&dir, &mut loaded);
+
+        for pos in &positions {
+            loaded.add_chunk(load_chunk(&dir, *pos, shader).unwrap());
+        }
+
+        let mut logs = 0;
+
+        for pos in &positions {
+            for x in 0..16 {
+                for z in 0..16 {
+                    for y in 0..CHUNK_HEIGHT as i32 {
+                        let world_pos = IVec3::new(pos.x * 16 + x, y, pos.y * 16 + z);
+                        assert_eq!(loaded.get_global_block(world_pos), expected.get_global_block(world_pos), "at {world_pos}");
+                        logs += (expected.get_global_block(world_pos) == block::LOG) as i32;
+                    }
+                }
+            }
+        }
+
+        assert!(logs > 0);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,245 @@
+// SPDX-FileCopyrightText: © 2025 - 2026 Elias Steininger <elias.st4600@gmail.com> and Project Contributors (see CONTRIBUTORS.md)
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use crate::world::biome::Biome;
+use crate::world::block;
+use crate::world::chunk::{Chunk, CHUNK_DIMENSION, CHUNK_HEIGHT};
+use crate::world::terrain::{TerrainGenerator, SEA_LEVEL};
+use glam::{IVec2, IVec3};
+use rand::rngs::StdRng;
+use rand::{RngExt, SeedableRng};
+use std::collections::HashMap;
+
+const KEEP: char = '_'; // Template cells that keep whatever the terrain has there
+
+pub enum Placement {
+    Surface {max_slope: i32}, // Largest height difference allowed below the footprint
+    Underground {min_y: i32, max_y: i32}
+}
+
+pub struct StructureProperties {
+    pub name: &'static str,
+    template: &'static str,
+    placement: Placement,
+    biomes: &'static [Biome], // Biomes the structure may appear in, empty means all
+    spacing: i32, // Size of the placement grid in chunks, at most one structure per cell
+    chance: f64 // Chance that a grid cell has the structure
+}
+
+pub const STRUCTURES: [StructureProperties; 3] = [
+    StructureProperties {name: "hut", template: include_str!("../../res/structures/hut.txt"), placement: Placement::Surface {max_slope: 2}, biomes: &[Biome::Plains, Biome::Forest], spacing: 6, chance: 0.6},
+    StructureProperties {name: "ruin", template: include_str!("../../res/structures/ruin.txt"), placement: Placement::Surface {max_slope: 3}, biomes: &[Biome::Plains, Biome::Desert, Biome::Mountains, Biome::Snow], spacing: 8, chance: 0.5},
+    StructureProperties {name: "dungeon", template: include_str!("../../res/structures/dungeon.txt"), placement: Placement::Underground {min_y: 12, max_y: 60}, biomes: &[], spacing: 4, chance: 0.4}
+];
+
+/// Blocks of a structure, parsed from a text file.
+/// The file starts with a legend (`C = cobblestone`), followed by the layers from the bottom up, each introduced by `---`.
+/// In a layer every line is a row along z and every character a block along x.
+pub struct Template {
+    size: IVec3,
+    blocks: Vec<Option<u8>> // None keeps the terrain
+}
+
+/// A structure at its position in the world
+pub struct PlacedStructure {
+    pub kind: usize, // Index into STRUCTURES
+    pub min: IVec3 // Minimum corner in world coordinates
+}
+
+impl Template {
+    pub fn parse(text: &str) -> Result<Template, String> {
+        let mut sections = text.split("\n---");
+        let mut legend: HashMap<char, Option<u8>> = HashMap::from([(KEEP, None)]);
+
+        for line in sections.next().unwrap_or_default().lines() {
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (key, name) = line.split_once('=').ok_or(format!("Invalid legend line '{line}'"))?;
+            let key = key.trim().chars().next().ok_or(format!("Missing key in '{line}'"))?;
+            let id = block::get_id(name.trim()).ok_or(format!("Unknown block '{}'", name.trim()))?;
+
+            legend.insert(key, Some(id));
+        }
+
+        let layers: Vec<Vec<&str>> = sections.map(|layer| layer.lines().map(str::trim).filter(|row| !row.is_empty()).collect()).collect();
+        let depth = layers.first().map_or(0, |rows| rows.len());
+        let width = layers.first().and_then(|rows| rows.first()).map_or(0, |row| row.chars().count());
+        let mut blocks = Vec::with_capacity(width * depth * layers.len());
+
+        for (y, rows) in layers.iter().enumerate() {
+            if rows.len() != depth {
+                return Err(format!("Layer {y} has {} rows instead of {depth}", rows.len()));
+            }
+
+            for row in rows {
+                if row.chars().count() != width {
+                    return Err(format!("Row '{row}' in layer {y} is not {width} blocks wide"));
+                }
+
+                for key in row.chars() {
+                    blocks.push(*legend.get(&key).ok_or(format!("Key '{key}' is not in the legend"))?);
+                }
+            }
+        }
+
+        Ok(Template {size: IVec3::new(width as i32, layers.len() as i32, depth as i32), blocks})
+    }
+
+    pub fn get_size(&self) -> IVec3 {self.size}
+
+    pub fn get_block(&self, pos: IVec3) -> Option<u8> {
+        self.blocks[(pos.y * self.size.z * self.size.x + pos.z * self.size.x + pos.x) as usize]
+    }
+}
+
+pub struct StructureGenerator {
+    seed: u64,
+    templates: Vec<Template>
+}
+
+impl StructureGenerator {
+    pub fn new(seed: u64) -> Self {
+        StructureGenerator {
+            seed,
+            templates: STRUCTURES.iter().map(|structure| Template::parse(structure.template).unwrap_or_else(|error| panic!("Invalid template '{}': {error}", structure.name))).collect()
+        }
+    }
+
+    /// The structure of a grid cell, if it has one. Structures never leave their cell, so a chunk only has to look at the cell it is in.
+    pub fn get_structure_in_cell(&self, terrain: &TerrainGenerator, kind: usize, cell: IVec2) -> Option<PlacedStructure> {
+        let properties = &STRUCTURES[kind];
+        let size = self.templates[kind].get_size();
+        let cell_size = properties.spacing * CHUNK_DIMENSION as i32;
+
+        let x = (cell.x as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+        let z = (cell.y as u64).wrapping_mul(0xC2B2_AE3D_27D4_EB4F);
+        let mut rng = StdRng::seed_from_u64(self.seed ^ x ^ z ^ (kind as u64 + 1).wrapping_mul(0x1656_67B1_9E37_79F9));
+
+        if !rng.random_bool(properties.chance) {
+            return None;
+        }
+
+        let min_x = cell.x * cell_size + rng.random_range(0..=cell_size - size.x);
+        let min_z = cell.y * cell_size + rng.random_range(0..=cell_size - size.z);
+        let center = IVec2::new(min_x + size.x / 2, min_z + size.z / 2);
+
+        if !properties.biomes.is_empty() && !properties.biomes.contains(&terrain.get_biome(center.x, center.y)) {
+            return None;
+        }
+
+        let min_y = match properties.placement {
+            Placement::Surface {max_slope} => {
+                let corners = [(min_x, min_z), (min_x + size.x - 1, min_z), (min_x, min_z + size.z - 1), (min_x + size.x - 1, min_z + size.z - 1), (center.x, center.y)];
+                let heights: Vec<i32> = corners.iter().map(|&(x, z)| terrain.get_surface_height(x, z)).collect();
+                let (lowest, highest) = (*heights.iter().min().unwrap(), *heights.iter().max().unwrap());
+
+                if highest - lowest > max_slope || lowest <= SEA_LEVEL || highest + size.y >= CHUNK_HEIGHT as i32 {
+                    return None;
+                }
+
+                lowest
+            }
+            Placement::Underground {min_y, max_y} => rng.random_range(min_y..=max_y)
+        };
+
+        Some(PlacedStructure {kind, min: IVec3::new(min_x, min_y, min_z)})
+    }
+
+    /// Every structure that overlaps the chunk
+    pub fn get_structures_in_chunk(&self, terrain: &TerrainGenerator, chunk_pos: IVec2) -> Vec<PlacedStructure> {
+        (0..STRUCTURES.len()).filter_map(|kind| {
+            let cell = IVec2::new(chunk_pos.x.div_euclid(STRUCTURES[kind].spacing), chunk_pos.y.div_euclid(STRUCTURES[kind].spacing));
+            let structure = self.get_structure_in_cell(terrain, kind, cell)?;
+
+            self.overlaps_chunk(&structure, chunk_pos).then_some(structure)
+        }).collect()
+    }
+
+    pub fn get_size(&self, structure: &PlacedStructure) -> IVec3 {self.templates[structure.kind].get_size()}
+
+    fn overlaps_chunk(&self, structure: &PlacedStructure, chunk_pos: IVec2) -> bool {
+        let min = chunk_pos * CHUNK_DIMENSION as i32;
+        let max = structure.min + self.get_size(structure);
+
+        structure.min.x < min.x + CHUNK_DIMENSION as i32 && max.x > min.x && structure.min.z < min.y + CHUNK_DIMENSION as i32 && max.z > min.y
+    }
+
+    /// Writes the part of the structure that lies inside the chunk, the other parts are written when their chunks generate
+    pub fn place(&self, chunk: &mut Chunk, structure: &PlacedStructure) {
+        let template = &self.templates[structure.kind];
+        let size = template.get_size();
+        let origin = IVec3::new(chunk.position.x * CHUNK_DIMENSION as i32, 0, chunk.position.y * CHUNK_DIMENSION as i32);
+
+        for x in 0..size.x {
+            for z in 0..size.z {
+                let local = structure.min + IVec3::new(x, 0, z) - origin;
+
+                if !(0..CHUNK_DIMENSION as i32).contains(&local.x) || !(0..CHUNK_DIMENSION as i32).contains(&local.z) {
+                    continue;
+                }
+
+                for y in 0..size.y {
+                    if let Some(id) = template.get_block(IVec3::new(x, y, z)) && (0..CHUNK_HEIGHT as i32).contains(&(local.y + y)) {
+                        chunk.set_block(local + IVec3::Y * y, id);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::test_util::generate;
+
+    const SEED: u64 = 12345678910;
+
+    #[test]
+    fn templates_parse() {
+        let generator = StructureGenerator::new(SEED);
+
+        assert_eq!(generator.templates[0].get_size(), IVec3::new(5, 5, 5));
+        assert_eq!(generator.templates[0].get_block(IVec3::new(0, 1, 0)), Some(block::LOG));
+        assert_eq!(generator.templates[1].get_block(IVec3::new(2, 1, 0)), None);
+        assert!(Template::parse("C = cobblestone\n---\nCX").is_err());
+    }
+
+    #[test]
+    fn structures_are_completed_across_chunks() {
+        let terrain = TerrainGenerator::new(SEED);
+        let structures = StructureGenerator::new(SEED);
+
+        for kind in 0..STRUCTURES.len() {
+            let structure = (0..64).find_map(|i| structures.get_structure_in_cell(&terrain, kind, IVec2::new(i % 8 - 4, i / 8 - 4))).unwrap();
+            let template = &structures.templates[kind];
+            let min_chunk = IVec2::new(structure.min.x, structure.min.z).div_euclid(IVec2::splat(CHUNK_DIMENSION as i32));
+            let max_chunk = IVec2::new(structure.min.x + template.size.x - 1, structure.min.z + template.size.z - 1).div_euclid(IVec2::splat(CHUNK_DIMENSION as i32));
+            let mut chunks = HashMap::new();
+
+            for x in min_chunk.x..=max_chunk.x {
+                for z in min_chunk.y..=max_chunk.y {
+                    chunks.insert(IVec2::new(x, z), generate(&terrain, IVec2::new(x, z)));
+                }
+            }
+
+            for x in 0..template.size.x {
+                for y in 0..template.size.y {
+                    for z in 0..template.size.z {
+                        let world_pos = structure.min + IVec3::new(x, y, z);
+                        let chunk_pos = IVec2::new(world_pos.x, world_pos.z).div_euclid(IVec2::splat(CHUNK_DIMENSION as i32));
+                        let local = IVec3::new(world_pos.x.rem_euclid(CHUNK_DIMENSION as i32), world_pos.y, world_pos.z.rem_euclid(CHUNK_DIMENSION as i32));
+
+                        if let Some(id) = template.get_block(IVec3::new(x, y, z)) {
+                            assert_eq!(chunks[&chunk_pos].get_block(local), id);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
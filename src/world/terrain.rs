@@ -0,0 +1,399 @@
+// SPDX-FileCopyrightText: © 2025 - 2026 Elias Steininger <elias.st4600@gmail.com> and Project Contributors (see CONTRIBUTORS.md)
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use crate::world::biome::Biome;
+use crate::world::block;
+use crate::world::chunk::{Chunk, CHUNK_DIMENSION, CHUNK_HEIGHT};
+use crate::world::decoration;
+use crate::world::generator::WorldGenerator;
+use crate::world::noise::Noise3D;
+use crate::world::ore;
+use crate::world::structure::StructureGenerator;
+use glam::IVec3;
+use rand::rngs::StdRng;
+use rand::{RngExt, SeedableRng};
+use std::collections::HashMap;
+use worldgen::noise::perlin::{Octaves, PerlinNoise};
+use worldgen::noise::NoiseProvider;
+
+pub const SEA_LEVEL: i32 = 112;
+
+const CLIMATE_SCALE: f64 = 0.0015; // Biomes are a few hundred blocks across
+const DETAIL_SCALE: f64 = 0.01;
+const BLEND_RADIUS: f32 = 12.0; // Heights are averaged over the biomes within this distance
+const BLEND_STEP: i32 = 4; // Spacing of the biome samples used for blending
+const MIN_FILLER_DEPTH: i32 = 3;
+const MAX_FILLER_DEPTH: i32 = 5;
+const ROCK_LINE: i32 = 140; // Mountains above this are bare stone
+const SNOW_LINE: i32 = 155; // Mountain peaks above this are covered in snow
+const BEDROCK_LEVEL: i32 = 0;
+const DENSITY_RANGE: i32 = 10; // Blocks above and below the blended height that are shaped by 3D noise
+const DENSITY_SCALE: f64 = 0.04;
+const DENSITY_STRENGTH: f64 = 6.0; // How many blocks the 3D noise can push the surface in or out
+const CHEESE_SCALE: f64 = 0.02;
+const CHEESE_THRESHOLD: f64 = 0.45;
+const SPAGHETTI_SCALE: f64 = 0.03;
+const SPAGHETTI_WIDTH: f64 = 0.06;
+const MIN_CAVE_LEVEL: i32 = 4;
+const LAVA_LEVEL: i32 = 10; // Caves below this are filled with lava
+const CAVE_SEA_FLOOR_DEPTH: i32 = 6; // Below the sea, caves stay at least this far below the floor
+const AMPLIFIED_HEIGHT_MULTIPLIER: f32 = 2.5;
+
+// Offsets to the world seed, so every noise layer is independent
+const HUMIDITY_SEED: u64 = 1000;
+const DETAIL_SEED: u64 = 2000;
+const DENSITY_SEED: u64 = 3000;
+const CHEESE_SEED: u64 = 4000;
+const SPAGHETTI_SEED_A: u64 = 5000;
+const SPAGHETTI_SEED_B: u64 = 6000;
+
+pub struct TerrainGenerator {
+    seed: u64,
+    noise: PerlinNoise,
+    density_noise: Noise3D,
+    cheese_noise: Noise3D,
+    spaghetti_noise_a: Noise3D,
+    spaghetti_noise_b: Noise3D,
+    structures: StructureGenerator,
+    height_multiplier: f32 // Scales how far the terrain deviates from the biome's base height
+}
+
+impl TerrainGenerator {
+    pub fn new(seed: u64) -> Self {
+        TerrainGenerator {
+            seed,
+            noise: PerlinNoise::new().set(Octaves::of(4)),
+            density_noise: Noise3D::new(seed.wrapping_add(DENSITY_SEED)),
+            cheese_noise: Noise3D::new(seed.wrapping_add(CHEESE_SEED)),
+            spaghetti_noise_a: Noise3D::new(seed.wrapping_add(SPAGHETTI_SEED_A)),
+            spaghetti_noise_b: Noise3D::new(seed.wrapping_add(SPAGHETTI_SEED_B)),
+            structures: StructureGenerator::new(seed),
+            height_multiplier: 1.0
+        }
+    }
+
+    /// Same biomes, but with much taller hills and mountains
+    pub fn amplified(seed: u64) -> Self {
+        TerrainGenerator {height_multiplier: AMPLIFIED_HEIGHT_MULTIPLIER, ..Self::new(seed)}
+    }
+
+    pub fn get_biome(&self, x: i32, z: i32) -> Biome {
+        let temperature = self.noise.generate(x as f64 * CLIMATE_SCALE, z as f64 * CLIMATE_SCALE, self.seed);
+        let humidity = self.noise.generate(x as f64 * CLIMATE_SCALE, z as f64 * CLIMATE_SCALE, self.seed.wrapping_add(HUMIDITY_SEED));
+
+        Biome::from_climate(temperature, humidity)
+    }
+
+    /// Approximate height of the topmost block of the column, ignoring overhangs and caves
+    pub fn get_surface_height(&self, x: i32, z: i32) -> i32 {
+        self.get_blended_height(x, z, &mut HashMap::new())
+    }
+
+    /// Mixes base height and height scale of all biomes within `BLEND_RADIUS`, weighted by distance, so biome borders are smooth slopes instead of cliffs
+    fn get_blended_height(&self, x: i32, z: i32, biome_cache: &mut HashMap<(i32, i32), Biome>) -> i32 {
+        let radius = BLEND_RADIUS as i32;
+        let mut base_height = 0.0;
+        let mut height_scale = 0.0;
+        let mut total_weight = 0.0;
+
+        for sample_x in ((x - radius).div_euclid(BLEND_STEP)..=(x + radius).div_euclid(BLEND_STEP)).map(|i| i * BLEND_STEP) {
+            for sample_z in ((z - radius).div_euclid(BLEND_STEP)..=(z + radius).div_euclid(BLEND_STEP)).map(|i| i * BLEND_STEP) {
+                let distance = ((sample_x - x) as f32).hypot((sample_z - z) as f32);
+                let weight = 1.0 - distance / BLEND_RADIUS;
+
+                if weight <= 0.0 {
+                    continue;
+                }
+
+                let biome = *biome_cache.entry((sample_x, sample_z)).or_insert_with(|| self.get_biome(sample_x, sample_z));
+                let properties = biome.get_properties();
+
+                base_height += properties.base_height * weight;
+                height_scale += properties.height_scale * weight;
+                total_weight += weight;
+            }
+        }
+
+        let detail = self.noise.generate(x as f64 * DETAIL_SCALE, z as f64 * DETAIL_SCALE, self.seed.wrapping_add(DETAIL_SEED)) as f32;
+
+        ((base_height + detail * height_scale * self.height_multiplier) / total_weight).round() as i32
+    }
+
+    /// Every chunk gets its own random generator derived from the seed, so a chunk always generates the same way
+    fn get_chunk_rng(&self, chunk: &Chunk) -> StdRng {
+        let x = (chunk.position.x as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+        let z = (chunk.position.y as u64).wrapping_mul(0xC2B2_AE3D_27D4_EB4F);
+
+        StdRng::seed_from_u64(self.seed ^ x ^ z)
+    }
+
+    /// Generates the chunk with its ores, decoration and structures and returns the decoration blocks that belong to other chunks, in world coordinates
+    pub fn generate_chunk(&self, chunk: &mut Chunk) -> Vec<(IVec3, u8)> {
+        let mut rng = self.get_chunk_rng(chunk);
+        let mut writes: Vec<(IVec3, u8)> = Vec::new();
+        let mut biome_cache: HashMap<(i32, i32), Biome> = HashMap::new();
+        let origin_x = chunk.position.x * CHUNK_DIMENSION as i32;
+        let origin_z = chunk.position.y * CHUNK_DIMENSION as i32;
+        let structures = self.structures.get_structures_in_chunk(self, chunk.position);
+
+        for x in 0..CHUNK_DIMENSION as i32 {
+            for z in 0..CHUNK_DIMENSION as i32 {
+                let (world_x, world_z) = (origin_x + x, origin_z + z);
+                let biome = self.get_biome(world_x, world_z);
+                let height = self.get_blended_height(world_x, world_z, &mut biome_cache);
+                let filler_depth = rng.random_range(MIN_FILLER_DEPTH..=MAX_FILLER_DEPTH);
+
+                // Walk down the column, counting how deep below the last air block each solid block is to layer surface, filler and stone
+                let mut depth = 0;
+                let mut top: Option<i32> = None;
+                let mut layers = Self::get_surface_blocks(biome, height);
+
+                for y in (0..=(height + DENSITY_RANGE).max(SEA_LEVEL).min(CHUNK_HEIGHT as i32 - 1)).rev() {
+                    let pos = IVec3::new(x, y, z);
+
+                    if y <= BEDROCK_LEVEL {
+                        chunk.set_block(pos, block::BEDROCK);
+                        continue;
+                    }
+
+                    if !self.is_solid(world_x, y, world_z, height) {
+                        depth = 0;
+
+                        if top.is_none() && y <= SEA_LEVEL {
+                            chunk.set_block(pos, block::WATER);
+                        }
+
+                        continue;
+                    }
+
+                    if depth == 0 {
+                        layers = Self::get_surface_blocks(biome, y);
+                        top.get_or_insert(y);
+                    }
+
+                    let id = if depth == 0 {layers.0} else if depth < filler_depth {layers.1} else {block::STONE};
+                    depth += 1;
+
+                    // Caves only open up to the surface on dry land, so the sea doesn't pour into them
+                    let may_carve = y >= MIN_CAVE_LEVEL && (top.unwrap() > SEA_LEVEL || y < top.unwrap() - CAVE_SEA_FLOOR_DEPTH);
+
+                    if may_carve && self.is_cave(world_x, y, world_z) {
+                        chunk.set_block(pos, if y <= LAVA_LEVEL {block::LAVA} else {block::AIR});
+                        continue;
+                    }
+
+                    chunk.set_block(pos, id);
+                }
+
+                // Nothing grows on or inside structures
+                let in_structure = structures.iter().any(|structure| {
+                    let max = structure.min + self.structures.get_size(structure);
+
+                    (structure.min.x..max.x).contains(&world_x) && (structure.min.z..max.z).contains(&world_z)
+                });
+
+                if let Some(top) = top && top > SEA_LEVEL && !in_structure {
+                    let surface = chunk.get_block(IVec3::new(x, top, z));
+
+                    decoration::decorate_column(IVec3::new(world_x, top, world_z), surface, biome, &mut rng, &mut writes);
+                }
+            }
+        }
+
+        ore::place_ores(chunk, &mut rng);
+
+        let origin = IVec3::new(origin_x, 0, origin_z);
+        let (inside, outside): (Vec<_>, Vec<_>) = writes.into_iter().partition(|&(pos, _)| {
+            (0..CHUNK_DIMENSION as i32).contains(&(pos.x - origin_x)) && (0..CHUNK_DIMENSION as i32).contains(&(pos.z - origin_z))
+        });
+
+        for (pos, id) in inside {
+            decoration::apply_write(chunk, pos - origin, id);
+        }
+
+        for structure in &structures {
+            self.structures.place(chunk, structure);
+        }
+
+        outside
+    }
+
+    /// Surface and filler block for a column of the biome whose surface is at `y`
+    fn get_surface_blocks(biome: Biome, y: i32) -> (u8, u8) {
+        let properties = biome.get_properties();
+
+        if y <= SEA_LEVEL - 3 {
+            (if biome == Biome::Ocean {properties.surface} else {block::GRAVEL}, properties.filler)
+        } else if y <= SEA_LEVEL + 1 {
+            (block::SAND, block::SAND)
+        } else if biome == Biome::Mountains && y > SNOW_LINE {
+            (block::SNOW, block::STONE)
+        } else if biome == Biome::Mountains && y > ROCK_LINE {
+            (block::STONE, block::STONE)
+        } else {
+            (properties.surface, properties.filler)
+        }
+    }
+
+    /// 3D density around the blended height, positive density is solid. This is what creates overhangs and arches.
+    fn is_solid(&self, x: i32, y: i32, z: i32, height: i32) -> bool {
+        if y < height - DENSITY_RANGE {
+            return true;
+        }
+
+        if y > height + DENSITY_RANGE {
+            return false;
+        }
+
+        let noise = self.density_noise.fractal(x as f64 * DENSITY_SCALE, y as f64 * DENSITY_SCALE * 1.5, z as f64 * DENSITY_SCALE, 2);
+
+        (height - y) as f64 + noise * DENSITY_STRENGTH > 0.0
+    }
+
+    /// Large open caverns where one noise is high (cheese caves) and long winding tunnels where two noises are both close to zero (spaghetti caves)
+    fn is_cave(&self, x: i32, y: i32, z: i32) -> bool {
+        let (x, y, z) = (x as f64, y as f64, z as f64);
+
+        if self.cheese_noise.sample(x * CHEESE_SCALE, y * CHEESE_SCALE * 2.0, z * CHEESE_SCALE) > CHEESE_THRESHOLD {
+            return true;
+        }
+
+        self.spaghetti_noise_a.sample(x * SPAGHETTI_SCALE, y * SPAGHETTI_SCALE, z * SPAGHETTI_SCALE).abs() < SPAGHETTI_WIDTH &&
+        self.spaghetti_noise_b.sample(x * SPAGHETTI_SCALE, y * SPAGHETTI_SCALE, z * SPAGHETTI_SCALE).abs() < SPAGHETTI_WIDTH
+    }
+}
+
+impl WorldGenerator for TerrainGenerator {
+    fn generate_chunk(&self, chunk: &mut Chunk) -> Vec<(IVec3, u8)> {
+        TerrainGenerator::generate_chunk(self, chunk)
+    }
+
+    fn get_spawn_height(&self, x: i32, z: i32) -> i32 {
+        self.get_surface_height(x, z)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::test_util::generate;
+    use glam::IVec2;
+
+    const SEED: u64 = 12345678910;
+
+    #[test]
+    fn every_biome_appears() {
+        let generator = TerrainGenerator::new(SEED);
+        let mut found: Vec<Biome> = Vec::new();
+
+        for x in -64..64 {
+            for z in -64..64 {
+                let biome = generator.get_biome(x * 64, z * 64);
+
+                if !found.contains(&biome) {
+                    found.push(biome);
+                }
+            }
+        }
+
+        assert_eq!(found.len(), 6);
+    }
+
+    #[test]
+    fn heights_blend_smoothly_across_biome_borders() {
+        let generator = TerrainGenerator::new(SEED);
+        let mut borders = 0;
+        let mut max_step = 0;
+
+        for x in -2000..2000 {
+            if generator.get_biome(x, 0) != generator.get_biome(x + 1, 0) {
+                borders += 1;
+            }
+
+            max_step = max_step.max((generator.get_surface_height(x, 0) - generator.get_surface_height(x + 1, 0)).abs());
+        }
+
+        assert!(borders > 0);
+        assert!(max_step <= 3);
+    }
+
+    #[test]
+    fn chunks_generate_deterministically() {
+        let generator = TerrainGenerator::new(SEED);
+        let first = generate(&generator, IVec2::new(3, -2));
+        let second = generate(&TerrainGenerator::new(SEED), IVec2::new(3, -2));
+
+        for x in 0..CHUNK_DIMENSION as i32 {
+            for z in 0..CHUNK_DIMENSION as i32 {
+                for y in 0..CHUNK_HEIGHT as i32 {
+                    assert_eq!(first.get_block(IVec3::new(x, y, z)), second.get_block(IVec3::new(x, y, z)));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn bedrock_floor_is_never_carved() {
+        let generator = TerrainGenerator::new(SEED);
+
+        for chunk_x in -2..2 {
+            let chunk = generate(&generator, IVec2::new(chunk_x, 0));
+
+            for x in 0..CHUNK_DIMENSION as i32 {
+                for z in 0..CHUNK_DIMENSION as i32 {
+                    assert_eq!(chunk.get_block(IVec3::new(x, 0, z)), block::BEDROCK);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn caves_are_carved_underground() {
+        let generator = TerrainGenerator::new(SEED);
+        let mut cave_blocks = 0;
+
+        for chunk_x in -2..2 {
+            let chunk = generate(&generator, IVec2::new(chunk_x, 0));
+
+            for x in 0..CHUNK_DIMENSION as i32 {
+                for z in 0..CHUNK_DIMENSION as i32 {
+                    // Air or lava well below the sea floor can only come from cave carving
+                    for y in MIN_CAVE_LEVEL..60 {
+                        let id = chunk.get_block(IVec3::new(x, y, z));
+
+                        if id == block::AIR || id == block::LAVA {
+                            cave_blocks += 1;
+                        }
+                    }
+                }
+            }
+        }
+
+        assert!(cave_blocks > 0);
+    }
+
+    #[test]
+    fn ores_stay_within_their_height_range() {
+        let generator = TerrainGenerator::new(SEED);
+        let mut found = [0; ore::ORES.len()];
+
+        for chunk_x in -2..2 {
+            let chunk = generate(&generator, IVec2::new(chunk_x, 0));
+
+            for x in 0..CHUNK_DIMENSION as i32 {
+                for z in 0..CHUNK_DIMENSION as i32 {
+                    for y in 0..CHUNK_HEIGHT as i32 {
+                        let id = chunk.get_block(IVec3::new(x, y, z));
+
+                        if let Some(index) = ore::ORES.iter().position(|ore| ore.id == id) {
+                            assert!((ore::ORES[index].min_height..=ore::ORES[index].max_height).contains(&y));
+                            found[index] += 1;
+                        }
+                    }
+                }
+            }
+        }
+
+        assert!(found.iter().all(|&count| count > 0));
+    }
+}
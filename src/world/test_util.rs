@@ -0,0 +1,68 @@
+// SPDX-FileCopyrightText: © 2025 - 2026 Elias Steininger <elias.st4600@gmail.com> and Project Contributors (see CONTRIBUTORS.md)
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use crate::world::block;
+use crate::world::chunk::Chunk;
+use crate::world::generator::WorldGenerator;
+use crate::world::world::World;
+use glam::{IVec2, IVec3};
+use glow::NativeProgram;
+use std::num::NonZeroU32;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static TEMP_PATH_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// Stands in for the chunk shader, tests never touch the GPU
+pub fn get_dummy_shader() -> NativeProgram {
+    NativeProgram(NonZeroU32::new(1).unwrap())
+}
+
+/// Empty chunk without any GPU state
+pub fn new_chunk(pos: IVec2) -> Chunk {
+    Chunk::new(pos, get_dummy_shader())
+}
+
+/// Chunk filled by `generator`, without its decoration
+pub fn generate(generator: &dyn WorldGenerator, pos: IVec2) -> Chunk {
+    let mut chunk = new_chunk(pos);
+    generator.generate_chunk(&mut chunk);
+    chunk
+}
+
+/// 3x3 chunks around the origin without any GPU state, with a single layer of `id` at height `y`
+pub fn world_with_floor(y: i32, id: u8) -> World {
+    let mut world = World::new();
+
+    for chunk_x in -1..=1 {
+        for chunk_z in -1..=1 {
+            let mut chunk = new_chunk(IVec2::new(chunk_x, chunk_z));
+
+            for x in 0..16 {
+                for z in 0..16 {
+                    chunk.set_block(IVec3::new(x, y, z), id);
+                }
+            }
+
+            world.add_chunk(chunk);
+        }
+    }
+
+    world
+}
+
+/// 3x3 chunks of air around the origin without any GPU state
+pub fn empty_world() -> World {
+    world_with_floor(0, block::AIR)
+}
+
+pub fn run_ticks(world: &mut World, ticks: u32) {
+    for _ in 0..ticks {
+        world.tick();
+    }
+}
+
+/// A path in the temp directory that no other test, and no other run of the tests at the same time, uses
+pub fn get_temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("brickbyte_test_{}_{}_{name}", std::process::id(), TEMP_PATH_COUNTER.fetch_add(1, Ordering::Relaxed)))
+}
@@ -1,14 +1,38 @@
 // SPDX-FileCopyrightText: © 2025 - 2026 Elias Steininger <elias.st4600@gmail.com> and Project Contributors (see CONTRIBUTORS.md)
 // SPDX-License-Identifier: GPL-3.0-or-later
 
+use crate::world::block;
+use crate::world::block::BlockShape;
 use crate::world::chunk::{Chunk, CHUNK_DIMENSION, CHUNK_HEIGHT};
+use crate::world::decoration;
+use crate::world::falling_block;
+use crate::world::falling_block::FallingBlock;
+use crate::world::fluid;
+use crate::world::random_tick;
+use crate::world::generator::WorldGenerator;
 use glam::{IVec2, IVec3, Mat4, Vec2, Vec3};
-use glow::{Context, HasContext, NativeTexture, Program};
+use glow::{Context, NativeTexture, Program};
+use rand::rngs::StdRng;
 use rand::RngExt;
-use std::collections::{HashMap, VecDeque};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+
+const MAX_BLOCK_UPDATES_PER_TICK: usize = 4096; // Left over updates wait for the next tick, so large changes can't stall a frame
+const NEIGHBORS: [IVec3; 6] = [IVec3::X, IVec3::NEG_X, IVec3::Y, IVec3::NEG_Y, IVec3::Z, IVec3::NEG_Z];
+pub const WORLD_BORDER: i32 = 1_000_000; // Farthest x and z the player can teleport to, chunk and block positions stay far from overflowing
 
 pub struct World {
     chunks: HashMap<IVec2, Chunk>,
+    dirty_chunks: HashSet<IVec2>, // Chunks whose lighting and mesh are out of date
+    current_tick: u64,
+    spawn: IVec3, // Block the player's feet are in when spawning, see `find_safe_spawn`
+    scheduled_ticks: BTreeMap<u64, Vec<IVec3>>, // Blocks to update, keyed by the tick they are due
+    scheduled_blocks: HashSet<IVec3>,
+    block_updates: VecDeque<IVec3>, // Blocks that changed or had a neighbour change, processed in order
+    queued_block_updates: HashSet<IVec3>,
+    rng: StdRng,
+    falling_blocks: Vec<FallingBlock>,
+    falling_block_meshes: HashMap<u8, Chunk>, // A unit cube per falling block type, built the first time one is drawn
+    pending_writes: HashMap<IVec2, Vec<(IVec3, u8)>> // Decoration for chunks that aren't loaded yet, in local coordinates
 }
 
 pub struct BlockRaycast {
@@ -19,35 +43,179 @@ pub struct BlockRaycast {
 impl World {
     pub fn new() -> Self {
         World {
-            chunks: HashMap::new()
+            chunks: HashMap::new(),
+            dirty_chunks: HashSet::new(),
+            current_tick: 0,
+            spawn: IVec3::ZERO,
+            scheduled_ticks: BTreeMap::new(),
+            scheduled_blocks: HashSet::new(),
+            block_updates: VecDeque::new(),
+            queued_block_updates: HashSet::new(),
+            rng: rand::make_rng(),
+            falling_blocks: Vec::new(),
+            falling_block_meshes: HashMap::new(),
+            pending_writes: HashMap::new()
         }
     }
     
-    pub fn insert_chunk(&mut self, pos: IVec2, shader: Program, noise_map: Vec<Vec<f64>>) {
+    pub fn insert_chunk(&mut self, pos: IVec2, shader: Program, generator: &dyn WorldGenerator) {
         let mut chunk = Chunk::new(IVec2::new(pos.x, pos.y), shader);
 
-        Self::generate_chunk(&mut chunk, noise_map);
+        let writes = generator.generate_chunk(&mut chunk);
+
+        self.add_chunk(chunk);
+
+        // Decoration reaching into other chunks is applied now if they exist, or once they are generated or loaded
+        for (world_pos, id) in writes {
+            let (chunk_pos, block_pos) = Self::world_to_local(world_pos);
+
+            if let Some(chunk) = self.chunks.get_mut(&chunk_pos) {
+                decoration::apply_write(chunk, block_pos, id);
+                self.dirty_chunks.insert(chunk_pos);
+            } else {
+                self.add_pending_write(world_pos, id);
+            }
+        }
+    }
+
+    pub fn add_chunk(&mut self, mut chunk: Chunk) {
         self.calculate_chunk_lighting(&mut chunk);
 
-        self.chunks.insert(IVec2::new(pos.x, pos.y), chunk);
+        let pos = chunk.position;
+        self.chunks.insert(pos, chunk);
+
+        // Generated and saved chunks both miss the decoration neighbours wrote into them while they weren't loaded
+        if let Some(writes) = self.pending_writes.remove(&pos) && let Some(chunk) = self.chunks.get_mut(&pos) {
+            for (block_pos, id) in writes {
+                decoration::apply_write(chunk, block_pos, id);
+            }
+        }
+
+        self.schedule_exposed_fluids(pos);
+
+        // The new chunk hides the border faces of its neighbours, so only those are remeshed with it
+        self.dirty_chunks.insert(pos);
+
+        for offset in [IVec2::X, IVec2::NEG_X, IVec2::Y, IVec2::NEG_Y] {
+            if self.has_chunk(pos + offset) {
+                self.dirty_chunks.insert(pos + offset);
+            }
+        }
     }
 
-    pub fn reload_world(&mut self, gl: &Context) {
-        let positions: Vec<IVec2> = self.chunks.keys().cloned().collect();
+    /// Decoration waiting for chunks that aren't loaded, in world coordinates
+    pub fn get_pending_writes(&self) -> Vec<(IVec3, u8)> {
+        self.pending_writes.iter().flat_map(|(chunk_pos, writes)| {
+            let origin = IVec3::new(chunk_pos.x * CHUNK_DIMENSION as i32, 0, chunk_pos.y * CHUNK_DIMENSION as i32);
+            writes.iter().map(move |(block_pos, id)| (origin + *block_pos, *id))
+        }).collect()
+    }
 
-        for pos in positions {
-            if let Some(mut chunk) = self.chunks.remove(&pos) {
-                self.generate_chunk_mesh(&mut chunk, gl);
+    /// Keeps a decoration write until its chunk is added, the chunk must not be loaded already
+    pub fn add_pending_write(&mut self, world_pos: IVec3, id: u8) {
+        let (chunk_pos, block_pos) = Self::world_to_local(world_pos);
+        self.pending_writes.entry(chunk_pos).or_default().push((block_pos, id));
+    }
+
+    /// Removes every chunk more than `distance` chunks away from `center` and returns them, so they can be saved.
+    /// Their GPU buffers are still allocated.
+    pub fn remove_chunks_outside(&mut self, center: IVec2, distance: i32) -> Vec<Chunk> {
+        let positions: Vec<IVec2> = self.chunks.keys().copied().filter(|pos| (*pos - center).abs().max_element() > distance).collect();
 
-                self.chunks.insert(pos, chunk);
+        positions.into_iter().filter_map(|pos| {
+            self.dirty_chunks.remove(&pos);
+            self.chunks.remove(&pos)
+        }).collect()
+    }
+
+    /// Generated and loaded fluids only flow after a block update, so fluids in the chunk and at the borders of its neighbours
+    /// that could flow into air are scheduled right away. Fluids surrounded by blocks stay still until one of them changes.
+    fn schedule_exposed_fluids(&mut self, chunk_pos: IVec2) {
+        let Some(chunk) = self.chunks.get(&chunk_pos) else {
+            return;
+        };
+
+        let origin = IVec3::new(chunk_pos.x * CHUNK_DIMENSION as i32, 0, chunk_pos.y * CHUNK_DIMENSION as i32);
+        let mut fluids: Vec<(IVec3, u8)> = Vec::new();
+
+        for x in -1..=CHUNK_DIMENSION as i32 {
+            for z in -1..=CHUNK_DIMENSION as i32 {
+                let inside_x = (0..CHUNK_DIMENSION as i32).contains(&x);
+                let inside_z = (0..CHUNK_DIMENSION as i32).contains(&z);
+                let inside = inside_x && inside_z;
+
+                // Fluids in the corners can't flow into this chunk
+                if !inside_x && !inside_z || !inside && !self.has_chunk(Self::world_to_local(origin + IVec3::new(x, 0, z)).0) {
+                    continue;
+                }
+
+                for y in 0..CHUNK_HEIGHT as i32 {
+                    let id = if inside {chunk.get_block(IVec3::new(x, y, z))} else {self.get_global_block(origin + IVec3::new(x, y, z))};
+
+                    if block::is_fluid(id) {
+                        fluids.push((origin + IVec3::new(x, y, z), id));
+                    }
+                }
+            }
+        }
+
+        for (world_pos, id) in fluids {
+            if self.can_flow_from(world_pos) {
+                self.schedule_tick(world_pos, fluid::get_tick_delay(id));
             }
         }
     }
-    
-    pub fn render_world(&mut self, gl: &Context, pv: Mat4, texture: Option<NativeTexture>) {
-        for (_pos, chunk) in &self.chunks {
+
+    /// Whether there is loaded air below or next to `world_pos`
+    fn can_flow_from(&self, world_pos: IVec3) -> bool {
+        [IVec3::NEG_Y, IVec3::X, IVec3::NEG_X, IVec3::Z, IVec3::NEG_Z].into_iter().any(|offset| {
+            let neighbor = world_pos + offset;
+            neighbor.y >= 0 && self.has_chunk(Self::world_to_local(neighbor).0) && self.get_global_block(neighbor) == block::AIR
+        })
+    }
+
+    /// Frees the GPU buffers of every chunk, the world can't be rendered afterwards
+    pub fn delete_buffers(&mut self, gl: &Context) {
+        for chunk in self.chunks.values_mut().chain(self.falling_block_meshes.values_mut()) {
+            chunk.delete_buffers(gl);
+        }
+
+        self.falling_block_meshes.clear();
+    }
+
+    pub fn get_chunks(&self) -> impl Iterator<Item = &Chunk> {self.chunks.values()}
+
+    pub fn has_chunk(&self, pos: IVec2) -> bool {self.chunks.contains_key(&pos)}
+
+    /// Renders the chunks at most `render_distance` chunks away from `center`
+    pub fn render_world(&mut self, gl: &Context, pv: Mat4, texture: Option<NativeTexture>, shader: Program, center: IVec2, render_distance: i32) {
+        for chunk in self.chunks.values().filter(|chunk| (chunk.position - center).abs().max_element() <= render_distance) {
             chunk.render(gl, pv, texture);
         }
+
+        self.render_falling_blocks(gl, pv, texture, shader);
+    }
+
+    /// Draws the cube of each falling block's type moved to the block's position
+    fn render_falling_blocks(&mut self, gl: &Context, pv: Mat4, texture: Option<NativeTexture>, shader: Program) {
+        for falling_block in &self.falling_blocks {
+            // Reuse the chunk buffer setup for the cube
+            let mesh = self.falling_block_meshes.entry(falling_block.id).or_insert_with(|| {
+                let (vertices, indices) = falling_block::get_cube_mesh(Self::get_texture_coords(&falling_block.id));
+                let mut mesh = Chunk::new(IVec2::ZERO, shader);
+
+                mesh.vertices = Some(vertices);
+                mesh.indices = Some(indices);
+                mesh.setup_buffers(gl);
+                mesh
+            });
+
+            mesh.render_with_model(gl, pv, falling_block.get_model(), texture);
+        }
+    }
+
+    pub fn spawn_falling_block(&mut self, falling_block: FallingBlock) {
+        self.falling_blocks.push(falling_block);
     }
     
     fn world_to_local(world_pos: IVec3) -> (IVec2, IVec3) {
@@ -67,29 +235,186 @@ impl World {
         0
     }
 
+    pub fn get_fluid_level(&self, world_pos: IVec3) -> u8 {
+        let (chunk_pos, block_pos) = Self::world_to_local(world_pos);
+
+        self.chunks.get(&chunk_pos).map_or(0, |chunk| chunk.get_fluid_level(block_pos))
+    }
+
+    /// Light level of the cell, unloaded cells count as fully lit
+    pub fn get_light(&self, world_pos: IVec3) -> u8 {
+        let (chunk_pos, block_pos) = Self::world_to_local(world_pos);
+
+        self.chunks.get(&chunk_pos).map_or(15, |chunk| chunk.get_light(block_pos))
+    }
+
+    pub fn get_rng(&mut self) -> &mut StdRng {&mut self.rng}
+
+    pub fn get_spawn(&self) -> IVec3 {self.spawn}
+
+    pub fn set_spawn(&mut self, spawn: IVec3) {self.spawn = spawn}
+
+    /// Whether the player can stand at `pos`: on solid ground that isn't leaves, with room for the body and no fluid
+    pub fn is_safe_spawn(&self, pos: IVec3) -> bool {
+        let ground = self.get_global_block(pos - IVec3::Y);
+        let is_free = |pos: IVec3| {
+            let id = self.get_global_block(pos);
+            !block::is_solid(id) && !block::is_fluid(id)
+        };
+
+        block::is_solid(ground) && ground != block::LEAVES && is_free(pos) && is_free(pos + IVec3::Y)
+    }
+
+    /// The closest column to `center`, at most `radius` blocks away on each axis, whose highest solid block is a safe place to stand on
+    pub fn find_safe_spawn(&self, center: IVec2, radius: i32) -> Option<IVec3> {
+        (0..=radius).flat_map(|ring| {
+            (-ring..=ring).flat_map(move |x| (-ring..=ring).map(move |z| IVec2::new(x, z)))
+                .filter(move |offset| offset.abs().max_element() == ring)
+        }).find_map(|offset| {
+            let column = center + offset;
+            let surface = (0..CHUNK_HEIGHT as i32).rev().find(|&y| block::is_solid(self.get_global_block(IVec3::new(column.x, y, column.y))))?;
+            let pos = IVec3::new(column.x, surface + 1, column.y);
+
+            self.is_safe_spawn(pos).then_some(pos)
+        })
+    }
+
+    /// Places a block and remeshes the affected chunks right away
     pub fn set_block(&mut self, world_pos: IVec3, id: u8, gl: &Context) {
+        self.update_block(world_pos, id, fluid::SOURCE_LEVEL);
+        self.rebuild_dirty_chunks(gl);
+    }
+
+    /// Places a block (with the given fluid level) without touching GPU state.
+    /// The affected chunks are marked dirty and the block and its six neighbours are queued for a block update.
+    pub fn update_block(&mut self, world_pos: IVec3, id: u8, fluid_level: u8) {
         let (chunk_pos, block_pos) = Self::world_to_local(world_pos);
 
-        let mut chunks_to_reload = vec![chunk_pos];
+        let Some(chunk) = self.chunks.get_mut(&chunk_pos) else {
+            return;
+        };
 
-        if block_pos.x == 0 {chunks_to_reload.push(chunk_pos - IVec2::X);}
-        if block_pos.x == CHUNK_DIMENSION as i32 - 1 {chunks_to_reload.push(chunk_pos + IVec2::X);}
-        if block_pos.z == 0 {chunks_to_reload.push(chunk_pos - IVec2::Y);}
-        if block_pos.z == CHUNK_DIMENSION as i32 - 1 {chunks_to_reload.push(chunk_pos + IVec2::Y);}
+        chunk.set_block(block_pos, id);
+        chunk.set_fluid_level(block_pos, fluid_level);
 
-        for c_pos in chunks_to_reload {
-            if let Some(mut chunk) = self.chunks.remove(&c_pos) {
-                if c_pos == chunk_pos {
-                    chunk.set_block(block_pos, id);
-                }
+        self.dirty_chunks.insert(chunk_pos);
+
+        if block_pos.x == 0 {self.dirty_chunks.insert(chunk_pos - IVec2::X);}
+        if block_pos.x == CHUNK_DIMENSION as i32 - 1 {self.dirty_chunks.insert(chunk_pos + IVec2::X);}
+        if block_pos.z == 0 {self.dirty_chunks.insert(chunk_pos - IVec2::Y);}
+        if block_pos.z == CHUNK_DIMENSION as i32 - 1 {self.dirty_chunks.insert(chunk_pos + IVec2::Y);}
+
+        self.queue_block_update(world_pos);
+
+        for offset in NEIGHBORS {
+            self.queue_block_update(world_pos + offset);
+        }
+    }
 
+    fn queue_block_update(&mut self, world_pos: IVec3) {
+        if self.queued_block_updates.insert(world_pos) {
+            self.block_updates.push_back(world_pos);
+        }
+    }
+
+    /// Relights and remeshes every chunk changed since the last call
+    pub fn rebuild_dirty_chunks(&mut self, gl: &Context) {
+        let dirty: Vec<IVec2> = self.dirty_chunks.drain().collect();
+
+        for c_pos in dirty {
+            if let Some(mut chunk) = self.chunks.remove(&c_pos) {
                 self.calculate_chunk_lighting(&mut chunk);
                 self.generate_chunk_mesh(&mut chunk, gl);
                 self.chunks.insert(c_pos, chunk);
             }
         }
     }
-    
+
+    /// Lets the block at `world_pos` react to a change of itself or one of its neighbours
+    fn handle_block_update(&mut self, world_pos: IVec3) {
+        let id = self.get_global_block(world_pos);
+
+        if block::is_fluid(id) {
+            self.schedule_tick(world_pos, fluid::get_tick_delay(id));
+        } else if block::has_gravity(id) {
+            self.schedule_tick(world_pos, falling_block::FALL_DELAY);
+        } else if block::needs_soil(id) && !block::is_soil(self.get_global_block(world_pos - IVec3::Y)) {
+            self.update_block(world_pos, block::AIR, 0);
+        }
+    }
+
+    /// Runs a scheduled update of the block at `world_pos` in `delay` ticks, unless one is already pending
+    pub fn schedule_tick(&mut self, world_pos: IVec3, delay: u64) {
+        if self.scheduled_blocks.insert(world_pos) {
+            self.scheduled_ticks.entry(self.current_tick + delay).or_default().push(world_pos);
+        }
+    }
+
+    fn run_scheduled_update(&mut self, world_pos: IVec3) {
+        let id = self.get_global_block(world_pos);
+
+        if block::is_fluid(id) {
+            fluid::update_fluid(self, world_pos);
+        } else if block::has_gravity(id) {
+            falling_block::update_gravity_block(self, world_pos);
+        }
+    }
+
+    /// Advances the world by one tick and runs every block update that is due
+    pub fn tick(&mut self) {
+        self.current_tick += 1;
+
+        while let Some(entry) = self.scheduled_ticks.first_entry() {
+            if *entry.key() > self.current_tick {
+                break;
+            }
+
+            for pos in entry.remove() {
+                self.scheduled_blocks.remove(&pos);
+                self.run_scheduled_update(pos);
+            }
+        }
+
+        for _ in 0..MAX_BLOCK_UPDATES_PER_TICK {
+            let Some(pos) = self.block_updates.pop_front() else {
+                break;
+            };
+
+            self.queued_block_updates.remove(&pos);
+            self.handle_block_update(pos);
+        }
+
+        let mut falling_blocks = std::mem::take(&mut self.falling_blocks);
+        falling_blocks.retain_mut(|falling_block| !falling_block.update(self));
+        falling_blocks.append(&mut self.falling_blocks);
+        self.falling_blocks = falling_blocks;
+
+        self.run_random_ticks();
+    }
+
+    /// Picks `RANDOM_TICK_SPEED` random cells in every 16 block high section of each loaded chunk and lets them grow, spread or decay
+    fn run_random_ticks(&mut self) {
+        let mut positions: Vec<IVec3> = Vec::new();
+
+        for chunk_pos in self.chunks.keys() {
+            let origin = IVec3::new(chunk_pos.x * CHUNK_DIMENSION as i32, 0, chunk_pos.y * CHUNK_DIMENSION as i32);
+
+            for section_y in (0..CHUNK_HEIGHT as i32).step_by(random_tick::SECTION_HEIGHT as usize) {
+                for _ in 0..random_tick::RANDOM_TICK_SPEED {
+                    positions.push(origin + IVec3::new(
+                        self.rng.random_range(0..CHUNK_DIMENSION as i32),
+                        section_y + self.rng.random_range(0..random_tick::SECTION_HEIGHT),
+                        self.rng.random_range(0..CHUNK_DIMENSION as i32)
+                    ));
+                }
+            }
+        }
+
+        for pos in positions {
+            random_tick::random_tick(self, pos);
+        }
+    }
+
     pub fn raycast_block(&self, origin: Vec3, direction: Vec3, max_distance: f32) -> Option<BlockRaycast> {
         let mut pos = origin.floor().as_ivec3();
         let step = IVec3::new(
@@ -112,7 +437,9 @@ impl World {
         let mut face_normal = IVec3::ZERO;
         
         while distance < max_distance {
-            if self.get_global_block(pos) != 0 {
+            let block_id = self.get_global_block(pos);
+
+            if block_id != block::AIR && !block::is_fluid(block_id) {
                 return Some(BlockRaycast {
                     block_pos: pos,
                     prev_block_pos: pos + face_normal
@@ -140,25 +467,6 @@ impl World {
         None
     }
 
-    fn generate_chunk(chunk: &mut Chunk, noise_map: Vec<Vec<f64>>) {
-        for x in 0..CHUNK_DIMENSION {
-            for y in 0..CHUNK_HEIGHT {
-                for z in 0..CHUNK_DIMENSION {
-                    let y_map: f64 = 50.0 + (noise_map[z as usize][x as usize] * 100.0); // 50 <= y_map <= 150
-                    let stone_y: f64 = rand::rng().random_range(3..6) as f64;
-
-                    if y as f64 <= (y_map - stone_y) {
-                        chunk.set_block(IVec3::new(x as i32, y as i32, z as i32), 3);
-                    } else if y as f64 <= (y_map - 1.0) {
-                        chunk.set_block(IVec3::new(x as i32, y as i32, z as i32), 2);
-                    } else if y as f64 <= y_map {
-                        chunk.set_block(IVec3::new(x as i32, y as i32, z as i32), 1);
-                    }
-                }
-            }
-        }
-    }
-
     fn calculate_chunk_lighting(&self, chunk: &mut Chunk) {
         let mut queue: VecDeque<IVec3> = VecDeque::new();
         chunk.light_map.fill(0);
@@ -168,7 +476,7 @@ impl World {
             for z in 0..CHUNK_DIMENSION as i32 {
                 for y in (0..CHUNK_HEIGHT as i32).rev() {
                     let pos = IVec3::new(x, y, z);
-                    if !self.block_is_air(chunk, pos) {
+                    if !self.block_is_transparent(chunk, pos) {
                         break;
                     }
                     chunk.set_light(pos, 15);
@@ -191,7 +499,7 @@ impl World {
             for offset in neighbors {
                 let neighbor_pos = pos + offset;
 
-                if self.block_is_air(chunk, neighbor_pos) {
+                if self.block_is_transparent(chunk, neighbor_pos) {
                     let neighbor_light = chunk.get_light(neighbor_pos);
 
                     if neighbor_light < current_light - 1 {
@@ -204,17 +512,7 @@ impl World {
     }
 
     fn generate_chunk_mesh(&self, chunk: &mut Chunk, gl: &Context){
-        unsafe {
-            if let Some(vao) = chunk.vertex_array_object {
-                gl.delete_vertex_array(vao);
-            }
-            if let Some(vbo) = chunk.vertex_buffer_object {
-                gl.delete_buffer(vbo);
-            }
-            if let Some(ebo) = chunk.element_buffer_object {
-                gl.delete_buffer(ebo);
-            }
-        }
+        chunk.delete_buffers(gl);
 
         let mut vertices: Vec<f32> = Vec::new();
         let mut indices: Vec<i32> = Vec::new();
@@ -233,33 +531,38 @@ impl World {
 
                     let texture_coords = Self::get_texture_coords(&block_type);
 
+                    if block::get_properties(block_type).shape == BlockShape::Cross {
+                        Self::add_cross(chunk, &mut vertices, &mut indices, block_pos, &mut index, texture_coords);
+                        continue;
+                    }
+
                     // Front face (Z + 1)
-                    if self.block_is_air(chunk, IVec3::new(x, y, z + 1)) {
+                    if self.face_is_visible(chunk, block_type, IVec3::new(x, y, z + 1)) {
                         self.add_face(chunk, &mut vertices, &mut indices, block_pos, IVec3::new(0, 0, 1), &mut index, texture_coords);
                     }
 
                     // Back Face (Z - 1)
-                    if self.block_is_air(chunk, IVec3::new(x, y, z - 1)) {
+                    if self.face_is_visible(chunk, block_type, IVec3::new(x, y, z - 1)) {
                         self.add_face(chunk, &mut vertices, &mut indices, block_pos, IVec3::new(0, 0, -1), &mut index, texture_coords);
                     }
 
                     // Top Face (Y + 1)
-                    if self.block_is_air(chunk, IVec3::new(x, y + 1, z)) {
+                    if self.face_is_visible(chunk, block_type, IVec3::new(x, y + 1, z)) {
                         self.add_face(chunk, &mut vertices, &mut indices, block_pos, IVec3::new(0, 1, 0), &mut index, texture_coords);
                     }
 
                     // Bottom Face (Y - 1)
-                    if self.block_is_air(chunk, IVec3::new(x, y - 1, z)) {
+                    if self.face_is_visible(chunk, block_type, IVec3::new(x, y - 1, z)) {
                         self.add_face(chunk, &mut vertices, &mut indices, block_pos, IVec3::new(0, -1, 0), &mut index, texture_coords);
                     }
 
                     // Left Face (X - 1)
-                    if self.block_is_air(chunk, IVec3::new(x - 1, y, z)) {
+                    if self.face_is_visible(chunk, block_type, IVec3::new(x - 1, y, z)) {
                         self.add_face(chunk, &mut vertices, &mut indices, block_pos, IVec3::new(-1, 0, 0), &mut index, texture_coords);
                     }
 
                     // Right Face (X + 1)
-                    if self.block_is_air(chunk, IVec3::new(x + 1, y, z)) {
+                    if self.face_is_visible(chunk, block_type, IVec3::new(x + 1, y, z)) {
                         self.add_face(chunk, &mut vertices, &mut indices, block_pos, IVec3::new(1, 0, 0), &mut index, texture_coords);
                     }
                 }
@@ -269,9 +572,10 @@ impl World {
         chunk.vertices = Some(vertices);
         chunk.indices = Some(indices);
 
-        chunk.setup_buffers(&gl);
+        chunk.setup_buffers(gl);
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn add_face(&self, chunk: &Chunk, vertices: &mut Vec<f32>, indices: &mut Vec<i32>, pos: IVec3, normal: IVec3, index: &mut i32, texture_coords: [Vec2; 4]) {
         let pos_float: Vec3 = Vec3::new(pos.x as f32 + 0.5, pos.y as f32 + 0.5, pos.z as f32 + 0.5);
         let mut face_vertices: [Vec3; 4] = [Vec3::ZERO; 4];
@@ -297,10 +601,10 @@ impl World {
                 face_vertices[3] = pos_float + Vec3::new(-0.5, 0.5, 0.5);
 
                 let adjacent = pos + normal;
-                face_light[0] = Self::vertex_ao(!self.block_is_air(chunk, adjacent + IVec3::new(-1,0,0)), !self.block_is_air(chunk, adjacent + IVec3::new(0,-1,0)), !self.block_is_air(chunk, adjacent + IVec3::new(-1,-1,0)));
-                face_light[1] = Self::vertex_ao(!self.block_is_air(chunk, adjacent + IVec3::new(1,0,0)), !self.block_is_air(chunk, adjacent + IVec3::new(0,-1,0)), !self.block_is_air(chunk, adjacent + IVec3::new(1,-1,0)));
-                face_light[2] = Self::vertex_ao(!self.block_is_air(chunk, adjacent + IVec3::new(1,0,0)), !self.block_is_air(chunk, adjacent + IVec3::new(0,1,0)), !self.block_is_air(chunk, adjacent + IVec3::new(1,1,0)));
-                face_light[3] = Self::vertex_ao(!self.block_is_air(chunk, adjacent + IVec3::new(-1,0,0)), !self.block_is_air(chunk, adjacent + IVec3::new(0,1,0)), !self.block_is_air(chunk, adjacent + IVec3::new(-1,1,0)));
+                face_light[0] = Self::vertex_ao(!self.block_is_transparent(chunk, adjacent + IVec3::new(-1,0,0)), !self.block_is_transparent(chunk, adjacent + IVec3::new(0,-1,0)), !self.block_is_transparent(chunk, adjacent + IVec3::new(-1,-1,0)));
+                face_light[1] = Self::vertex_ao(!self.block_is_transparent(chunk, adjacent + IVec3::new(1,0,0)), !self.block_is_transparent(chunk, adjacent + IVec3::new(0,-1,0)), !self.block_is_transparent(chunk, adjacent + IVec3::new(1,-1,0)));
+                face_light[2] = Self::vertex_ao(!self.block_is_transparent(chunk, adjacent + IVec3::new(1,0,0)), !self.block_is_transparent(chunk, adjacent + IVec3::new(0,1,0)), !self.block_is_transparent(chunk, adjacent + IVec3::new(1,1,0)));
+                face_light[3] = Self::vertex_ao(!self.block_is_transparent(chunk, adjacent + IVec3::new(-1,0,0)), !self.block_is_transparent(chunk, adjacent + IVec3::new(0,1,0)), !self.block_is_transparent(chunk, adjacent + IVec3::new(-1,1,0)));
             }
 
             // Back Face
@@ -311,10 +615,10 @@ impl World {
                 face_vertices[3] = pos_float + Vec3::new(0.5, 0.5, -0.5);
 
                 let adjacent = pos + normal;
-                face_light[0] = Self::vertex_ao(!self.block_is_air(chunk, adjacent + IVec3::new(1,0,0)), !self.block_is_air(chunk, adjacent + IVec3::new(0,-1,0)), !self.block_is_air(chunk, adjacent + IVec3::new(1,-1,0)));
-                face_light[1] = Self::vertex_ao(!self.block_is_air(chunk, adjacent + IVec3::new(-1,0,0)), !self.block_is_air(chunk, adjacent + IVec3::new(0,-1,0)), !self.block_is_air(chunk, adjacent + IVec3::new(-1,-1,0)));
-                face_light[2] = Self::vertex_ao(!self.block_is_air(chunk, adjacent + IVec3::new(-1,0,0)), !self.block_is_air(chunk, adjacent + IVec3::new(0,1,0)), !self.block_is_air(chunk, adjacent + IVec3::new(-1,1,0)));
-                face_light[3] = Self::vertex_ao(!self.block_is_air(chunk, adjacent + IVec3::new(1,0,0)), !self.block_is_air(chunk, adjacent + IVec3::new(0,1,0)), !self.block_is_air(chunk, adjacent + IVec3::new(1,1,0)));
+                face_light[0] = Self::vertex_ao(!self.block_is_transparent(chunk, adjacent + IVec3::new(1,0,0)), !self.block_is_transparent(chunk, adjacent + IVec3::new(0,-1,0)), !self.block_is_transparent(chunk, adjacent + IVec3::new(1,-1,0)));
+                face_light[1] = Self::vertex_ao(!self.block_is_transparent(chunk, adjacent + IVec3::new(-1,0,0)), !self.block_is_transparent(chunk, adjacent + IVec3::new(0,-1,0)), !self.block_is_transparent(chunk, adjacent + IVec3::new(-1,-1,0)));
+                face_light[2] = Self::vertex_ao(!self.block_is_transparent(chunk, adjacent + IVec3::new(-1,0,0)), !self.block_is_transparent(chunk, adjacent + IVec3::new(0,1,0)), !self.block_is_transparent(chunk, adjacent + IVec3::new(-1,1,0)));
+                face_light[3] = Self::vertex_ao(!self.block_is_transparent(chunk, adjacent + IVec3::new(1,0,0)), !self.block_is_transparent(chunk, adjacent + IVec3::new(0,1,0)), !self.block_is_transparent(chunk, adjacent + IVec3::new(1,1,0)));
             }
 
             // Top Face
@@ -325,10 +629,10 @@ impl World {
                 face_vertices[3] = pos_float + Vec3::new(-0.5, 0.5, 0.5);
 
                 let adjacent = pos + normal;
-                face_light[0] = Self::vertex_ao(!self.block_is_air(chunk, adjacent + IVec3::new(-1,0,0)), !self.block_is_air(chunk, adjacent + IVec3::new(0,0,-1)), !self.block_is_air(chunk, adjacent + IVec3::new(-1,0,-1)));
-                face_light[1] = Self::vertex_ao(!self.block_is_air(chunk, adjacent + IVec3::new(1,0,0)), !self.block_is_air(chunk, adjacent + IVec3::new(0,0,-1)), !self.block_is_air(chunk, adjacent + IVec3::new(1,0,-1)));
-                face_light[2] = Self::vertex_ao(!self.block_is_air(chunk, adjacent + IVec3::new(1,0,0)), !self.block_is_air(chunk, adjacent + IVec3::new(0,0,1)), !self.block_is_air(chunk, adjacent + IVec3::new(1,0,1)));
-                face_light[3] = Self::vertex_ao(!self.block_is_air(chunk, adjacent + IVec3::new(-1,0,0)), !self.block_is_air(chunk, adjacent + IVec3::new(0,0,1)), !self.block_is_air(chunk, adjacent + IVec3::new(-1,0,1)));
+                face_light[0] = Self::vertex_ao(!self.block_is_transparent(chunk, adjacent + IVec3::new(-1,0,0)), !self.block_is_transparent(chunk, adjacent + IVec3::new(0,0,-1)), !self.block_is_transparent(chunk, adjacent + IVec3::new(-1,0,-1)));
+                face_light[1] = Self::vertex_ao(!self.block_is_transparent(chunk, adjacent + IVec3::new(1,0,0)), !self.block_is_transparent(chunk, adjacent + IVec3::new(0,0,-1)), !self.block_is_transparent(chunk, adjacent + IVec3::new(1,0,-1)));
+                face_light[2] = Self::vertex_ao(!self.block_is_transparent(chunk, adjacent + IVec3::new(1,0,0)), !self.block_is_transparent(chunk, adjacent + IVec3::new(0,0,1)), !self.block_is_transparent(chunk, adjacent + IVec3::new(1,0,1)));
+                face_light[3] = Self::vertex_ao(!self.block_is_transparent(chunk, adjacent + IVec3::new(-1,0,0)), !self.block_is_transparent(chunk, adjacent + IVec3::new(0,0,1)), !self.block_is_transparent(chunk, adjacent + IVec3::new(-1,0,1)));
             }
 
             // Bottom Face
@@ -339,10 +643,10 @@ impl World {
                 face_vertices[3] = pos_float + Vec3::new(-0.5, -0.5, -0.5);
 
                 let adjacent = pos + normal;
-                face_light[0] = Self::vertex_ao(!self.block_is_air(chunk, adjacent + IVec3::new(-1,0,0)), !self.block_is_air(chunk, adjacent + IVec3::new(0,0,1)), !self.block_is_air(chunk, adjacent + IVec3::new(-1,0,1)));
-                face_light[1] = Self::vertex_ao(!self.block_is_air(chunk, adjacent + IVec3::new(1,0,0)), !self.block_is_air(chunk, adjacent + IVec3::new(0,0,1)), !self.block_is_air(chunk, adjacent + IVec3::new(1,0,1)));
-                face_light[2] = Self::vertex_ao(!self.block_is_air(chunk, adjacent + IVec3::new(1,0,0)), !self.block_is_air(chunk, adjacent + IVec3::new(0,0,-1)), !self.block_is_air(chunk, adjacent + IVec3::new(1,0,-1)));
-                face_light[3] = Self::vertex_ao(!self.block_is_air(chunk, adjacent + IVec3::new(-1,0,0)), !self.block_is_air(chunk, adjacent + IVec3::new(0,0,-1)), !self.block_is_air(chunk, adjacent + IVec3::new(-1,0,-1)));
+                face_light[0] = Self::vertex_ao(!self.block_is_transparent(chunk, adjacent + IVec3::new(-1,0,0)), !self.block_is_transparent(chunk, adjacent + IVec3::new(0,0,1)), !self.block_is_transparent(chunk, adjacent + IVec3::new(-1,0,1)));
+                face_light[1] = Self::vertex_ao(!self.block_is_transparent(chunk, adjacent + IVec3::new(1,0,0)), !self.block_is_transparent(chunk, adjacent + IVec3::new(0,0,1)), !self.block_is_transparent(chunk, adjacent + IVec3::new(1,0,1)));
+                face_light[2] = Self::vertex_ao(!self.block_is_transparent(chunk, adjacent + IVec3::new(1,0,0)), !self.block_is_transparent(chunk, adjacent + IVec3::new(0,0,-1)), !self.block_is_transparent(chunk, adjacent + IVec3::new(1,0,-1)));
+                face_light[3] = Self::vertex_ao(!self.block_is_transparent(chunk, adjacent + IVec3::new(-1,0,0)), !self.block_is_transparent(chunk, adjacent + IVec3::new(0,0,-1)), !self.block_is_transparent(chunk, adjacent + IVec3::new(-1,0,-1)));
             }
 
             // Left Face
@@ -353,10 +657,10 @@ impl World {
                 face_vertices[3] = pos_float + Vec3::new(-0.5, 0.5, -0.5);
 
                 let adjacent = pos + normal;
-                face_light[0] = Self::vertex_ao(!self.block_is_air(chunk, adjacent + IVec3::new(0,-1,0)), !self.block_is_air(chunk, adjacent + IVec3::new(0,0,-1)), !self.block_is_air(chunk, adjacent + IVec3::new(0,-1,-1)));
-                face_light[1] = Self::vertex_ao(!self.block_is_air(chunk, adjacent + IVec3::new(0,-1,0)), !self.block_is_air(chunk, adjacent + IVec3::new(0,0,1)), !self.block_is_air(chunk, adjacent + IVec3::new(0,-1,1)));
-                face_light[2] = Self::vertex_ao(!self.block_is_air(chunk, adjacent + IVec3::new(0,1,0)), !self.block_is_air(chunk, adjacent + IVec3::new(0,0,1)), !self.block_is_air(chunk, adjacent + IVec3::new(0,1,1)));
-                face_light[3] = Self::vertex_ao(!self.block_is_air(chunk, adjacent + IVec3::new(0,1,0)), !self.block_is_air(chunk, adjacent + IVec3::new(0,0,-1)), !self.block_is_air(chunk, adjacent + IVec3::new(0,1,-1)));
+                face_light[0] = Self::vertex_ao(!self.block_is_transparent(chunk, adjacent + IVec3::new(0,-1,0)), !self.block_is_transparent(chunk, adjacent + IVec3::new(0,0,-1)), !self.block_is_transparent(chunk, adjacent + IVec3::new(0,-1,-1)));
+                face_light[1] = Self::vertex_ao(!self.block_is_transparent(chunk, adjacent + IVec3::new(0,-1,0)), !self.block_is_transparent(chunk, adjacent + IVec3::new(0,0,1)), !self.block_is_transparent(chunk, adjacent + IVec3::new(0,-1,1)));
+                face_light[2] = Self::vertex_ao(!self.block_is_transparent(chunk, adjacent + IVec3::new(0,1,0)), !self.block_is_transparent(chunk, adjacent + IVec3::new(0,0,1)), !self.block_is_transparent(chunk, adjacent + IVec3::new(0,1,1)));
+                face_light[3] = Self::vertex_ao(!self.block_is_transparent(chunk, adjacent + IVec3::new(0,1,0)), !self.block_is_transparent(chunk, adjacent + IVec3::new(0,0,-1)), !self.block_is_transparent(chunk, adjacent + IVec3::new(0,1,-1)));
             }
 
             // Right Face
@@ -367,15 +671,26 @@ impl World {
                 face_vertices[3] = pos_float + Vec3::new(0.5, 0.5, 0.5);
 
                 let adjacent = pos + normal;
-                face_light[0] = Self::vertex_ao(!self.block_is_air(chunk, adjacent + IVec3::new(0,-1,0)), !self.block_is_air(chunk, adjacent + IVec3::new(0,0,1)), !self.block_is_air(chunk, adjacent + IVec3::new(0,-1,1)));
-                face_light[1] = Self::vertex_ao(!self.block_is_air(chunk, adjacent + IVec3::new(0,-1,0)), !self.block_is_air(chunk, adjacent + IVec3::new(0,0,-1)), !self.block_is_air(chunk, adjacent + IVec3::new(0,-1,-1)));
-                face_light[2] = Self::vertex_ao(!self.block_is_air(chunk, adjacent + IVec3::new(0,1,0)), !self.block_is_air(chunk, adjacent + IVec3::new(0,0,-1)), !self.block_is_air(chunk, adjacent + IVec3::new(0,1,-1)));
-                face_light[3] = Self::vertex_ao(!self.block_is_air(chunk, adjacent + IVec3::new(0,1,0)), !self.block_is_air(chunk, adjacent + IVec3::new(0,0,1)), !self.block_is_air(chunk, adjacent + IVec3::new(0,1,1)));
+                face_light[0] = Self::vertex_ao(!self.block_is_transparent(chunk, adjacent + IVec3::new(0,-1,0)), !self.block_is_transparent(chunk, adjacent + IVec3::new(0,0,1)), !self.block_is_transparent(chunk, adjacent + IVec3::new(0,-1,1)));
+                face_light[1] = Self::vertex_ao(!self.block_is_transparent(chunk, adjacent + IVec3::new(0,-1,0)), !self.block_is_transparent(chunk, adjacent + IVec3::new(0,0,-1)), !self.block_is_transparent(chunk, adjacent + IVec3::new(0,-1,-1)));
+                face_light[2] = Self::vertex_ao(!self.block_is_transparent(chunk, adjacent + IVec3::new(0,1,0)), !self.block_is_transparent(chunk, adjacent + IVec3::new(0,0,-1)), !self.block_is_transparent(chunk, adjacent + IVec3::new(0,1,-1)));
+                face_light[3] = Self::vertex_ao(!self.block_is_transparent(chunk, adjacent + IVec3::new(0,1,0)), !self.block_is_transparent(chunk, adjacent + IVec3::new(0,0,1)), !self.block_is_transparent(chunk, adjacent + IVec3::new(0,1,1)));
             }
 
             _ => {}
         }
 
+        // Fluids that aren't covered by more of the same fluid get a lowered surface depending on their level
+        let block_type = chunk.get_block(pos);
+
+        if block::is_fluid(block_type) && self.get_block_at(chunk, pos + IVec3::Y) != block_type {
+            let surface_y = pos.y as f32 + fluid::get_surface_height(chunk.get_fluid_level(pos));
+
+            for vertex in face_vertices.iter_mut().filter(|vertex| vertex.y > pos.y as f32 + 0.5) {
+                vertex.y = surface_y;
+            }
+        }
+
         for i in 0..4 {
             vertices.push(face_vertices[i].x);
             vertices.push(face_vertices[i].y);
@@ -385,16 +700,48 @@ impl World {
             vertices.push(face_light[i] * face_brightness * sunlight_brightness);
         }
 
-        indices.push(*index + 0);
+        indices.push(*index);
         indices.push(*index + 1);
         indices.push(*index + 2);
         indices.push(*index + 2);
         indices.push(*index + 3);
-        indices.push(*index + 0);
+        indices.push(*index);
         *index += 4;
     }
 
-    fn get_texture_coords(block_type: &u8) -> [Vec2; 4] {
+    /// Two quads crossing diagonally through the block, visible from both sides
+    fn add_cross(chunk: &Chunk, vertices: &mut Vec<f32>, indices: &mut Vec<i32>, pos: IVec3, index: &mut i32, texture_coords: [Vec2; 4]) {
+        let pos_float: Vec3 = pos.as_vec3();
+        let brightness = chunk.get_light(pos) as f32 / 15.0;
+
+        let quads: [[Vec3; 4]; 2] = [
+            [Vec3::new(0.0, 1.0, 0.0), Vec3::new(1.0, 1.0, 1.0), Vec3::new(1.0, 0.0, 1.0), Vec3::new(0.0, 0.0, 0.0)],
+            [Vec3::new(0.0, 1.0, 1.0), Vec3::new(1.0, 1.0, 0.0), Vec3::new(1.0, 0.0, 0.0), Vec3::new(0.0, 0.0, 1.0)]
+        ];
+
+        for quad in quads {
+            for i in 0..4 {
+                let vertex = pos_float + quad[i];
+
+                vertices.push(vertex.x);
+                vertices.push(vertex.y);
+                vertices.push(vertex.z);
+                vertices.push(texture_coords[i].x);
+                vertices.push(texture_coords[i].y);
+                vertices.push(brightness);
+            }
+
+            indices.push(*index);
+            indices.push(*index + 1);
+            indices.push(*index + 2);
+            indices.push(*index + 2);
+            indices.push(*index + 3);
+            indices.push(*index);
+            *index += 4;
+        }
+    }
+
+    pub fn get_texture_coords(block_type: &u8) -> [Vec2; 4] {
         let atlas_size: u8 = 16;
         let tile_size: f32 = 1.0 / atlas_size as f32;
         let index: u8 = block_type - 1;
@@ -411,12 +758,22 @@ impl World {
         ]
     }
 
-    fn block_is_air(&self, chunk: &Chunk, pos: IVec3) -> bool {
+    fn get_block_at(&self, chunk: &Chunk, pos: IVec3) -> u8 {
         if pos.x >= 0 && pos.x < CHUNK_DIMENSION as i32 && pos.z >= 0 && pos.z < CHUNK_DIMENSION as i32 && pos.y >= 0 && pos.y < CHUNK_HEIGHT as i32 {
-            return chunk.get_block(pos) == 0;
+            return chunk.get_block(pos);
         }
 
-        self.get_global_block(pos + IVec3::new(chunk.position.x * CHUNK_DIMENSION as i32, 0, chunk.position.y * CHUNK_DIMENSION as i32)) == 0
+        self.get_global_block(pos + IVec3::new(chunk.position.x * CHUNK_DIMENSION as i32, 0, chunk.position.y * CHUNK_DIMENSION as i32))
+    }
+
+    fn block_is_transparent(&self, chunk: &Chunk, pos: IVec3) -> bool {
+        !block::is_opaque(self.get_block_at(chunk, pos))
+    }
+
+    fn face_is_visible(&self, chunk: &Chunk, block_type: u8, neighbor_pos: IVec3) -> bool {
+        let neighbor = self.get_block_at(chunk, neighbor_pos);
+
+        !block::is_opaque(neighbor) && neighbor != block_type
     }
 
     fn vertex_ao(side1: bool, side2: bool, corner: bool) -> f32 {
@@ -433,3 +790,97 @@ impl World {
         1.0 - (occlusion as f32 * 0.25)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::test_util::{new_chunk, run_ticks, world_with_floor};
+
+    const FLOOR_Y: i32 = 10;
+
+    #[test]
+    fn spawn_is_on_the_closest_safe_surface() {
+        let mut world = world_with_floor(FLOOR_Y, block::STONE);
+        let surface = IVec3::new(0, FLOOR_Y + 1, 0);
+
+        assert_eq!(world.find_safe_spawn(IVec2::ZERO, 4), Some(surface));
+
+        world.update_block(IVec3::new(0, FLOOR_Y, 0), block::WATER, 0);
+        world.update_block(IVec3::new(1, FLOOR_Y + 1, 0), block::LEAVES, 0);
+        world.update_block(IVec3::new(0, FLOOR_Y + 2, 1), block::BRICKS, 0);
+
+        assert!(!world.is_safe_spawn(surface));
+        assert!(!world.is_safe_spawn(IVec3::new(0, FLOOR_Y + 1, 1)));
+        assert_eq!(world.find_safe_spawn(IVec2::ZERO, 4), Some(IVec3::new(-1, FLOOR_Y + 1, -1)));
+        assert_eq!(world.find_safe_spawn(IVec2::new(100, 100), 4), None);
+    }
+
+    #[test]
+    fn chunks_outside_the_distance_are_removed() {
+        let mut world = world_with_floor(FLOOR_Y, block::STONE);
+        let removed = world.remove_chunks_outside(IVec2::NEG_ONE, 1);
+
+        assert_eq!(removed.len(), 5);
+        assert!(removed.iter().all(|chunk| chunk.position.x == 1 || chunk.position.y == 1));
+        assert_eq!(world.get_chunks().count(), 4);
+        assert!(world.dirty_chunks.iter().all(|pos| world.has_chunk(*pos)));
+    }
+
+    #[test]
+    fn new_chunks_remesh_only_their_neighbors() {
+        let mut world = world_with_floor(FLOOR_Y, block::STONE);
+        world.dirty_chunks.clear();
+
+        world.add_chunk(new_chunk(IVec2::new(2, 0)));
+
+        assert_eq!(world.dirty_chunks, HashSet::from([IVec2::new(2, 0), IVec2::new(1, 0)]));
+    }
+
+    #[test]
+    fn change_queues_block_and_neighbors_once() {
+        let mut world = world_with_floor(FLOOR_Y, block::STONE);
+        let pos = IVec3::new(2, FLOOR_Y + 1, 2);
+
+        world.update_block(pos, block::BRICKS, 0);
+        world.update_block(pos, block::COBBLESTONE, 0);
+
+        assert_eq!(world.block_updates.len(), 7);
+
+        run_ticks(&mut world, 1);
+        assert!(world.block_updates.is_empty());
+    }
+
+    #[test]
+    fn block_updates_over_budget_carry_over() {
+        let mut world = world_with_floor(FLOOR_Y, block::STONE);
+
+        for x in -16..32 {
+            for z in -16..32 {
+                world.update_block(IVec3::new(x, FLOOR_Y + 2, z), block::BRICKS, 0);
+            }
+        }
+
+        let queued = world.block_updates.len();
+        assert!(queued > MAX_BLOCK_UPDATES_PER_TICK);
+
+        run_ticks(&mut world, 1);
+        assert_eq!(world.block_updates.len(), queued - MAX_BLOCK_UPDATES_PER_TICK);
+
+        run_ticks(&mut world, 10);
+        assert!(world.block_updates.is_empty());
+    }
+
+    #[test]
+    fn plant_pops_off_without_soil() {
+        let mut world = world_with_floor(FLOOR_Y, block::GRASS);
+        let sapling = IVec3::new(2, FLOOR_Y + 1, 2);
+
+        world.update_block(sapling, block::SAPLING, 0);
+        run_ticks(&mut world, 1);
+        assert_eq!(world.get_global_block(sapling), block::SAPLING);
+
+        world.update_block(sapling - IVec3::Y, block::STONE, 0);
+        run_ticks(&mut world, 1);
+        assert_eq!(world.get_global_block(sapling), block::AIR);
+    }
+}